@@ -0,0 +1,112 @@
+#include "genesis.h"
+
+#include <stdio.h>
+#include <string.h>
+#include <stdlib.h>
+
+// open an audio file and write its decoded samples to a raw file, for
+// comparing against the output of another decoder
+
+static int usage(char *exe) {
+    fprintf(stderr, "Usage: %s inputfile --dump outputfile\n"
+            "\n"
+            "Writes interleaved 64-bit float samples in native byte order.\n"
+            "Channel layout and sample rate are printed to stderr.\n", exe);
+    return 1;
+}
+
+static int report_error(enum GenesisError err) {
+    fprintf(stderr, "Error: %s\n", genesis_strerror(err));
+    return 1;
+}
+
+int main(int argc, char **argv) {
+    char *input_filename = NULL;
+    char *output_filename = NULL;
+
+    for (int i = 1; i < argc; i += 1) {
+        char *arg = argv[i];
+        if (arg[0] == '-' && arg[1] == '-') {
+            arg += 2;
+            if (i + 1 >= argc) {
+                return usage(argv[0]);
+            } else if (strcmp(arg, "dump") == 0) {
+                output_filename = argv[++i];
+            } else {
+                return usage(argv[0]);
+            }
+        } else if (!input_filename) {
+            input_filename = arg;
+        } else {
+            return usage(argv[0]);
+        }
+    }
+
+    if (!input_filename || !output_filename)
+        return usage(argv[0]);
+
+    struct GenesisContext *context;
+    int err = genesis_context_create(&context);
+    if (err)
+        return report_error(err);
+
+    struct GenesisAudioFile *audio_file;
+    err = genesis_audio_file_load(context, input_filename, &audio_file);
+    if (err)
+        return report_error(err);
+
+    const struct SoundIoChannelLayout *channel_layout = genesis_audio_file_channel_layout(audio_file);
+    int channel_count = channel_layout->channel_count;
+    long frame_count = genesis_audio_file_frame_count(audio_file);
+    int sample_rate = genesis_audio_file_sample_rate(audio_file);
+    if (channel_layout->name)
+        fprintf(stderr, "Channels: %d (%s)\n", channel_count, channel_layout->name);
+    else
+        fprintf(stderr, "Channels: %d\n", channel_count);
+    fprintf(stderr, "Sample rate: %d\n", sample_rate);
+    static const char *tag_names[] = {"title", "artist", "album"};
+    for (int i = 0; i < 3; i += 1) {
+        const char *value = genesis_audio_file_tag(audio_file, tag_names[i]);
+        if (value)
+            fprintf(stderr, "%s: %s\n", tag_names[i], value);
+    }
+    fprintf(stderr, "%ld frames\n", frame_count);
+    fprintf(stderr, "Duration: %.3fs\n", genesis_audio_file_duration(audio_file));
+    double container_duration = genesis_audio_file_container_duration(audio_file);
+    if (container_duration >= 0.0)
+        fprintf(stderr, "Container duration: %.3fs\n", container_duration);
+
+    FILE *out_file = fopen(output_filename, "wb");
+    if (!out_file) {
+        fprintf(stderr, "unable to open %s\n", output_filename);
+        return 1;
+    }
+
+    struct GenesisAudioFileIterator *its = malloc(channel_count * sizeof(struct GenesisAudioFileIterator));
+    if (!its)
+        return report_error(GenesisErrorNoMem);
+    for (int ch = 0; ch < channel_count; ch += 1)
+        its[ch] = genesis_audio_file_iterator(audio_file, ch, 0);
+
+    for (long frame = 0; frame < frame_count; frame += 1) {
+        for (int ch = 0; ch < channel_count; ch += 1) {
+            struct GenesisAudioFileIterator *it = &its[ch];
+            if (frame >= it->end)
+                genesis_audio_file_iterator_next(it);
+            double sample = it->ptr[frame - it->start];
+            if (fwrite(&sample, sizeof(double), 1, out_file) != 1) {
+                fprintf(stderr, "unable to write %s\n", output_filename);
+                return 1;
+            }
+        }
+    }
+
+    if (fclose(out_file)) {
+        fprintf(stderr, "unable to write %s\n", output_filename);
+        return 1;
+    }
+
+    free(its);
+    genesis_audio_file_destroy(audio_file);
+    genesis_context_destroy(context);
+}
@@ -0,0 +1,281 @@
+#include "genesis.h"
+
+#include <stdio.h>
+#include <string.h>
+#include <stdlib.h>
+#include <png.h>
+
+// open an audio file and draw its waveform into a PNG image, for file
+// managers or media servers to use as a preview. an output filename ending
+// in .svg gets the same waveform as vector paths with a time axis instead,
+// which stays crisp at any size in papers and reports.
+
+static int usage(char *exe) {
+    fprintf(stderr, "Usage: %s inputfile outputfile.png|outputfile.svg [--width W] [--height H]\n"
+            "       [--peaks minmax|absmax|average]\n"
+            "\n"
+            "Default size is 256x64.\n"
+            "\n"
+            "Peaks sum up the samples under each column:\n"
+            "  minmax   lowest to highest sample, the exact envelope (default)\n"
+            "  absmax   loudest sample, mirrored around the zero line\n"
+            "  average  mean loudness, mirrored, smoother and shorter than absmax\n", exe);
+    return 1;
+}
+
+static int report_error(enum GenesisError err) {
+    fprintf(stderr, "Error: %s\n", genesis_strerror(err));
+    return 1;
+}
+
+enum PeakStrategy {
+    PeakStrategyMinMax,
+    PeakStrategyAbsMax,
+    PeakStrategyAverage,
+};
+
+static const unsigned char bg_color[4] = {0x18, 0x18, 0x18, 0xff};
+static const unsigned char fg_color[4] = {0x4f, 0xc1, 0xe9, 0xff};
+
+static void set_pixel(unsigned char *pixels, int width, int x, int y, const unsigned char *color) {
+    memcpy(pixels + (y * width + x) * 4, color, 4);
+}
+
+static int write_png(const char *filename, unsigned char *pixels, int width, int height) {
+    FILE *f = fopen(filename, "wb");
+    if (!f)
+        return -1;
+
+    png_structp png_ptr = png_create_write_struct(PNG_LIBPNG_VER_STRING, NULL, NULL, NULL);
+    if (!png_ptr) {
+        fclose(f);
+        return -1;
+    }
+    png_infop info_ptr = png_create_info_struct(png_ptr);
+    if (!info_ptr) {
+        png_destroy_write_struct(&png_ptr, NULL);
+        fclose(f);
+        return -1;
+    }
+    if (setjmp(png_jmpbuf(png_ptr))) {
+        png_destroy_write_struct(&png_ptr, &info_ptr);
+        fclose(f);
+        return -1;
+    }
+
+    png_init_io(png_ptr, f);
+    png_set_IHDR(png_ptr, info_ptr, width, height, 8, PNG_COLOR_TYPE_RGB_ALPHA,
+            PNG_INTERLACE_NONE, PNG_COMPRESSION_TYPE_DEFAULT, PNG_FILTER_TYPE_DEFAULT);
+    png_write_info(png_ptr, info_ptr);
+    for (int y = 0; y < height; y += 1)
+        png_write_row(png_ptr, pixels + y * width * 4);
+    png_write_end(png_ptr, NULL);
+    png_destroy_write_struct(&png_ptr, &info_ptr);
+
+    return fclose(f);
+}
+
+static bool ends_with(const char *str, const char *suffix) {
+    size_t str_len = strlen(str);
+    size_t suffix_len = strlen(suffix);
+    return str_len >= suffix_len && strcmp(str + str_len - suffix_len, suffix) == 0;
+}
+
+// the smallest of 1, 2 or 5 times a power of 10 seconds that puts no more
+// than 8 ticks on the axis
+static double tick_interval(double duration) {
+    double step = 0.001;
+    for (;;) {
+        if (duration / step <= 8.0)
+            return step;
+        if (duration / (step * 2.0) <= 8.0)
+            return step * 2.0;
+        if (duration / (step * 5.0) <= 8.0)
+            return step * 5.0;
+        step *= 10.0;
+    }
+}
+
+// the envelope is one closed path, along the column maxima left to right and
+// back along the minima, so it scales without gaps between columns
+static int write_svg(const char *filename, const float *col_min, const float *col_max,
+        int width, int height, double duration)
+{
+    FILE *f = fopen(filename, "w");
+    if (!f)
+        return -1;
+
+    float half_height = height / 2.0f;
+    fprintf(f, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"%d\" height=\"%d\" "
+            "viewBox=\"0 0 %d %d\">\n", width, height, width, height);
+    fprintf(f, "<rect width=\"%d\" height=\"%d\" fill=\"#%02x%02x%02x\"/>\n",
+            width, height, bg_color[0], bg_color[1], bg_color[2]);
+
+    fprintf(f, "<path fill=\"#%02x%02x%02x\" d=\"", fg_color[0], fg_color[1], fg_color[2]);
+    for (int x = 0; x < width; x += 1) {
+        float y = half_height - col_max[x] * half_height;
+        fprintf(f, "%s%d %.2f %d %.2f", (x == 0) ? "M" : " L", x, y, x + 1, y);
+    }
+    for (int x = width - 1; x >= 0; x -= 1) {
+        float y = half_height - col_min[x] * half_height;
+        fprintf(f, " L%d %.2f %d %.2f", x + 1, y, x, y);
+    }
+    fprintf(f, " Z\"/>\n");
+
+    // time axis along the zero line, with ticks labeled in seconds
+    fprintf(f, "<g stroke=\"#808080\" stroke-width=\"0.5\" fill=\"#808080\" "
+            "font-family=\"sans-serif\" font-size=\"8\">\n");
+    fprintf(f, "<line x1=\"0\" y1=\"%.2f\" x2=\"%d\" y2=\"%.2f\"/>\n",
+            half_height, width, half_height);
+    if (duration > 0.0) {
+        double interval = tick_interval(duration);
+        for (int i = 0; i * interval <= duration; i += 1) {
+            double seconds = i * interval;
+            double x = seconds / duration * width;
+            fprintf(f, "<line x1=\"%.2f\" y1=\"%.2f\" x2=\"%.2f\" y2=\"%.2f\"/>\n",
+                    x, half_height - 3.0f, x, half_height + 3.0f);
+            fprintf(f, "<text x=\"%.2f\" y=\"%d\" stroke=\"none\">%gs</text>\n",
+                    x + 2.0, height - 2, seconds);
+        }
+    }
+    fprintf(f, "</g>\n");
+    fprintf(f, "</svg>\n");
+
+    return fclose(f);
+}
+
+int main(int argc, char **argv) {
+    char *input_filename = NULL;
+    char *output_filename = NULL;
+    int width = 256;
+    int height = 64;
+    enum PeakStrategy peak_strategy = PeakStrategyMinMax;
+
+    for (int i = 1; i < argc; i += 1) {
+        char *arg = argv[i];
+        if (arg[0] == '-' && arg[1] == '-') {
+            arg += 2;
+            if (i + 1 >= argc) {
+                return usage(argv[0]);
+            } else if (strcmp(arg, "width") == 0) {
+                width = atoi(argv[++i]);
+            } else if (strcmp(arg, "height") == 0) {
+                height = atoi(argv[++i]);
+            } else if (strcmp(arg, "peaks") == 0) {
+                char *name = argv[++i];
+                if (strcmp(name, "minmax") == 0)
+                    peak_strategy = PeakStrategyMinMax;
+                else if (strcmp(name, "absmax") == 0)
+                    peak_strategy = PeakStrategyAbsMax;
+                else if (strcmp(name, "average") == 0)
+                    peak_strategy = PeakStrategyAverage;
+                else
+                    return usage(argv[0]);
+            } else {
+                return usage(argv[0]);
+            }
+        } else if (!input_filename) {
+            input_filename = arg;
+        } else if (!output_filename) {
+            output_filename = arg;
+        } else {
+            return usage(argv[0]);
+        }
+    }
+
+    if (!input_filename || !output_filename || width <= 0 || height <= 0)
+        return usage(argv[0]);
+
+    struct GenesisContext *context;
+    int err = genesis_context_create(&context);
+    if (err)
+        return report_error(err);
+
+    struct GenesisAudioFile *audio_file;
+    err = genesis_audio_file_load(context, input_filename, &audio_file);
+    if (err)
+        return report_error(err);
+
+    int channel_count = genesis_audio_file_channel_layout(audio_file)->channel_count;
+    long frame_count = genesis_audio_file_frame_count(audio_file);
+
+    // the lowest and highest sample of any channel in each column, and the
+    // loudness of every sample in it added up
+    float *col_min = malloc(width * sizeof(float));
+    float *col_max = malloc(width * sizeof(float));
+    double *col_sum = malloc(width * sizeof(double));
+    long *col_count = malloc(width * sizeof(long));
+    unsigned char *pixels = malloc(width * height * 4);
+    if (!col_min || !col_max || !col_sum || !col_count || !pixels)
+        return report_error(GenesisErrorNoMem);
+    for (int x = 0; x < width; x += 1) {
+        col_min[x] = 0.0f;
+        col_max[x] = 0.0f;
+        col_sum[x] = 0.0;
+        col_count[x] = 0;
+    }
+
+    for (int ch = 0; ch < channel_count; ch += 1) {
+        struct GenesisAudioFileIterator it = genesis_audio_file_iterator(audio_file, ch, 0);
+        for (long frame = 0; frame < frame_count; frame += 1) {
+            if (frame >= it.end)
+                genesis_audio_file_iterator_next(&it);
+            float sample = it.ptr[frame - it.start];
+            int x = (int)(frame * width / frame_count);
+            if (sample < col_min[x])
+                col_min[x] = sample;
+            if (sample > col_max[x])
+                col_max[x] = sample;
+            col_sum[x] += (sample < 0.0f) ? -sample : sample;
+            col_count[x] += 1;
+        }
+    }
+
+    // the other strategies draw a band mirrored around the zero line
+    for (int x = 0; x < width; x += 1) {
+        float peak;
+        if (peak_strategy == PeakStrategyAbsMax)
+            peak = (-col_min[x] > col_max[x]) ? -col_min[x] : col_max[x];
+        else if (peak_strategy == PeakStrategyAverage)
+            peak = col_count[x] ? (float)(col_sum[x] / col_count[x]) : 0.0f;
+        else
+            continue;
+        col_min[x] = -peak;
+        col_max[x] = peak;
+    }
+
+    int write_err;
+    if (ends_with(output_filename, ".svg")) {
+        double duration = frame_count / (double)genesis_audio_file_sample_rate(audio_file);
+        write_err = write_svg(output_filename, col_min, col_max, width, height, duration);
+    } else {
+        for (int y = 0; y < height; y += 1) {
+            for (int x = 0; x < width; x += 1)
+                set_pixel(pixels, width, x, y, bg_color);
+        }
+        float half_height = height / 2.0f;
+        for (int x = 0; x < width; x += 1) {
+            int top = (int)(half_height - col_max[x] * half_height);
+            int bottom = (int)(half_height - col_min[x] * half_height);
+            if (top < 0)
+                top = 0;
+            if (bottom > height - 1)
+                bottom = height - 1;
+            for (int y = top; y <= bottom; y += 1)
+                set_pixel(pixels, width, x, y, fg_color);
+        }
+        write_err = write_png(output_filename, pixels, width, height);
+    }
+    if (write_err) {
+        fprintf(stderr, "unable to write %s\n", output_filename);
+        return 1;
+    }
+
+    free(pixels);
+    free(col_count);
+    free(col_sum);
+    free(col_max);
+    free(col_min);
+    genesis_audio_file_destroy(audio_file);
+    genesis_context_destroy(context);
+}
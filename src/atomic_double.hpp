@@ -13,6 +13,12 @@ public:
         while (!_value.compare_exchange_weak(current_value, current_value + x)) {}
     }
 
+    // stores x if it is larger than the current value
+    void store_max(double x) {
+        double current_value = _value.load();
+        while (current_value < x && !_value.compare_exchange_weak(current_value, x)) {}
+    }
+
     void store(double x) {
         _value.store(x);
     }
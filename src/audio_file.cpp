@@ -1,7 +1,9 @@
 #include "audio_file.hpp"
+#include "genesis.hpp"
 #include "os.hpp"
 
 #include <stdint.h>
+#include <ctype.h>
 
 static const SoundIoFormat sample_format_list[] = {
     SoundIoFormatU8,
@@ -99,87 +101,87 @@ static const int bit_rate_list[] = {
 static const float int24_min = -8388608.0f;
 static const float int24_max = 8388607.0f;
 
-static int import_frame_uint8(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_uint8(const AVFrame *avframe, List<Channel> &channels) {
     uint8_t *ptr = avframe->extended_data[0];
     double min = 0.0;
     double max = (double)UINT8_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
     for (int frame = 0; frame < avframe->nb_samples; frame += 1) {
-        for (int ch = 0; ch < audio_file->channels.length(); ch += 1, ptr += 1) {
+        for (int ch = 0; ch < channels.length(); ch += 1, ptr += 1) {
             uint8_t sample = *ptr;
             double dbl_sample = (((double)sample) - min) / half_range - 1.0;
-            err = err || audio_file->channels.at(ch).samples.append(dbl_sample);
+            err = err || channels.at(ch).samples.append(dbl_sample);
         }
     }
     return err;
 }
 
-static int import_frame_int16(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_int16(const AVFrame *avframe, List<Channel> &channels) {
     uint8_t *ptr = avframe->extended_data[0];
     double min = (double)INT16_MIN;
     double max = (double)INT16_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
     for (int frame = 0; frame < avframe->nb_samples; frame += 1) {
-        for (int ch = 0; ch < audio_file->channels.length(); ch += 1, ptr += 2) {
+        for (int ch = 0; ch < channels.length(); ch += 1, ptr += 2) {
             int16_t *sample = reinterpret_cast<int16_t*>(ptr);
             double dbl_sample = (((double)*sample) - min) / half_range - 1.0;
-            err = err || audio_file->channels.at(ch).samples.append(dbl_sample);
+            err = err || channels.at(ch).samples.append(dbl_sample);
         }
     }
     return err;
 }
 
-static int import_frame_int32(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_int32(const AVFrame *avframe, List<Channel> &channels) {
     uint8_t *ptr = avframe->extended_data[0];
     double min = (double)INT32_MIN;
     double max = (double)INT32_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
     for (int frame = 0; frame < avframe->nb_samples; frame += 1) {
-        for (int ch = 0; ch < audio_file->channels.length(); ch += 1, ptr += 4) {
+        for (int ch = 0; ch < channels.length(); ch += 1, ptr += 4) {
             int32_t *sample = reinterpret_cast<int32_t*>(ptr);
             double dbl_sample = (((double)*sample) - min) / half_range - 1.0;
-            err = err || audio_file->channels.at(ch).samples.append(dbl_sample);
+            err = err || channels.at(ch).samples.append(dbl_sample);
         }
     }
     return err;
 }
 
-static int import_frame_float(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_float(const AVFrame *avframe, List<Channel> &channels) {
     uint8_t *ptr = avframe->extended_data[0];
     int err = 0;
     for (int frame = 0; frame < avframe->nb_samples; frame += 1) {
-        for (int ch = 0; ch < audio_file->channels.length(); ch += 1, ptr += 4) {
+        for (int ch = 0; ch < channels.length(); ch += 1, ptr += 4) {
             float *sample = reinterpret_cast<float*>(ptr);
             double dbl_sample = *sample;
-            err = err || audio_file->channels.at(ch).samples.append(dbl_sample);
+            err = err || channels.at(ch).samples.append(dbl_sample);
         }
     }
     return err;
 }
 
-static int import_frame_double(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_double(const AVFrame *avframe, List<Channel> &channels) {
     uint8_t *ptr = avframe->extended_data[0];
     int err = 0;
     for (int frame = 0; frame < avframe->nb_samples; frame += 1) {
-        for (int ch = 0; ch < audio_file->channels.length(); ch += 1, ptr += 8) {
+        for (int ch = 0; ch < channels.length(); ch += 1, ptr += 8) {
             double *sample = reinterpret_cast<double*>(ptr);
-            err = err || audio_file->channels.at(ch).samples.append(*sample);
+            err = err || channels.at(ch).samples.append(*sample);
         }
     }
     return err;
 }
 
-static int import_frame_uint8_planar(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_uint8_planar(const AVFrame *avframe, List<Channel> &channels) {
     double min = 0.0;
     double max = (double)UINT8_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
-    for (int ch = 0; ch < audio_file->channels.length(); ch += 1) {
+    for (int ch = 0; ch < channels.length(); ch += 1) {
         uint8_t *ptr = avframe->extended_data[ch];
-        Channel *channel = &audio_file->channels.at(ch);
+        Channel *channel = &channels.at(ch);
         for (int frame = 0; frame < avframe->nb_samples; frame += 1, ptr += 1) {
             uint8_t sample = *ptr;
             double dbl_sample = (((double)sample) - min) / half_range - 1.0;
@@ -189,14 +191,14 @@ static int import_frame_uint8_planar(const AVFrame *avframe, GenesisAudioFile *a
     return err;
 }
 
-static int import_frame_int16_planar(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_int16_planar(const AVFrame *avframe, List<Channel> &channels) {
     double min = (double)INT16_MIN;
     double max = (double)INT16_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
-    for (int ch = 0; ch < audio_file->channels.length(); ch += 1) {
+    for (int ch = 0; ch < channels.length(); ch += 1) {
         uint8_t *ptr = avframe->extended_data[ch];
-        Channel *channel = &audio_file->channels.at(ch);
+        Channel *channel = &channels.at(ch);
         for (int frame = 0; frame < avframe->nb_samples; frame += 1, ptr += 2) {
             int16_t *sample = reinterpret_cast<int16_t*>(ptr);
             double dbl_sample = (((double)*sample) - min) / half_range - 1.0;
@@ -206,14 +208,14 @@ static int import_frame_int16_planar(const AVFrame *avframe, GenesisAudioFile *a
     return err;
 }
 
-static int import_frame_int32_planar(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_int32_planar(const AVFrame *avframe, List<Channel> &channels) {
     double min = (double)INT32_MIN;
     double max = (double)INT32_MAX;
     double half_range = max / 2.0 - min / 2.0;
     int err = 0;
-    for (int ch = 0; ch < audio_file->channels.length(); ch += 1) {
+    for (int ch = 0; ch < channels.length(); ch += 1) {
         uint8_t *ptr = avframe->extended_data[ch];
-        Channel *channel = &audio_file->channels.at(ch);
+        Channel *channel = &channels.at(ch);
         for (int frame = 0; frame < avframe->nb_samples; frame += 1, ptr += 4) {
             int32_t *sample = reinterpret_cast<int32_t*>(ptr);
             double dbl_sample = (((double)*sample) - min) / half_range - 1.0;
@@ -223,11 +225,11 @@ static int import_frame_int32_planar(const AVFrame *avframe, GenesisAudioFile *a
     return err;
 }
 
-static int import_frame_float_planar(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_float_planar(const AVFrame *avframe, List<Channel> &channels) {
     int err = 0;
-    for (int ch = 0; ch < audio_file->channels.length(); ch += 1) {
+    for (int ch = 0; ch < channels.length(); ch += 1) {
         uint8_t *ptr = avframe->extended_data[ch];
-        Channel *channel = &audio_file->channels.at(ch);
+        Channel *channel = &channels.at(ch);
         for (int frame = 0; frame < avframe->nb_samples; frame += 1, ptr += 4) {
             float *sample = reinterpret_cast<float*>(ptr);
             double dbl_sample = *sample;
@@ -237,11 +239,11 @@ static int import_frame_float_planar(const AVFrame *avframe, GenesisAudioFile *a
     return err;
 }
 
-static int import_frame_double_planar(const AVFrame *avframe, GenesisAudioFile *audio_file) {
+static int import_frame_double_planar(const AVFrame *avframe, List<Channel> &channels) {
     int err = 0;
-    for (int ch = 0; ch < audio_file->channels.length(); ch += 1) {
+    for (int ch = 0; ch < channels.length(); ch += 1) {
         uint8_t *ptr = avframe->extended_data[ch];
-        Channel *channel = &audio_file->channels.at(ch);
+        Channel *channel = &channels.at(ch);
         for (int frame = 0; frame < avframe->nb_samples; frame += 1, ptr += 8) {
             double *sample = reinterpret_cast<double*>(ptr);
             err = err || channel->samples.append(*sample);
@@ -254,9 +256,9 @@ static int decode_interrupt_cb(void *ctx) {
     return 0;
 }
 
-static int decode_frame(GenesisAudioFile *audio_file, AVPacket *pkt,
+static int decode_frame(List<Channel> &channels, AVPacket *pkt,
         AVCodecContext *codec_ctx, AVFrame *in_frame,
-        int (*import_frame)(const AVFrame *, GenesisAudioFile *))
+        int (*import_frame)(const AVFrame *, List<Channel> &))
 {
     AVPacket pkt_temp = *pkt;
     bool new_packet = true;
@@ -282,7 +284,7 @@ static int decode_frame(GenesisAudioFile *audio_file, AVPacket *pkt,
             continue;
         }
 
-        int err = import_frame(in_frame, audio_file);
+        int err = import_frame(in_frame, channels);
         if (err)
             return -GenesisErrorNoMem;
         decoded_byte_count += in_frame->nb_samples *
@@ -343,28 +345,109 @@ static int channel_layout_init_from_ffmpeg(uint64_t ffmpeg_channel_layout, Sound
     return 0;
 }
 
-int genesis_audio_file_load(struct GenesisContext *context,
-        const char *input_filename, struct GenesisAudioFile **out_audio_file)
-{
-    *out_audio_file = nullptr;
-    GenesisAudioFile *audio_file = create_zero<GenesisAudioFile>();
-    if (!audio_file) {
-        genesis_audio_file_destroy(audio_file);
+static int download_error(int av_err) {
+    if (av_err == AVERROR(ENOMEM))
         return GenesisErrorNoMem;
+    else if (av_err == AVERROR(ECONNREFUSED))
+        return GenesisErrorConnectionRefused;
+    else if (av_err == AVERROR(ENOENT) || av_err == AVERROR_HTTP_NOT_FOUND)
+        return GenesisErrorFileNotFound;
+    else if (av_err == AVERROR_HTTP_UNAUTHORIZED || av_err == AVERROR_HTTP_FORBIDDEN)
+        return GenesisErrorPermissionDenied;
+    else
+        return GenesisErrorFileAccess;
+}
+
+int genesis_audio_file_download(const char *url, const char *output_filename,
+        void (*on_progress)(void *userdata, long received, long total), void *userdata)
+{
+    // this can be called before there is a GenesisContext
+    int err;
+    if ((err = os_init(audio_file_init)))
+        return err;
+
+    FILE *out_file = fopen(output_filename, "wb");
+    if (!out_file)
+        return GenesisErrorFileAccess;
+
+    AVIOContext *avio = nullptr;
+    int av_err = avio_open2(&avio, url, AVIO_FLAG_READ, nullptr, nullptr);
+    if (av_err < 0) {
+        fclose(out_file);
+        return download_error(av_err);
     }
 
-    audio_file->ic = avformat_alloc_context();
-    if (!audio_file->ic) {
-        genesis_audio_file_destroy(audio_file);
-        return GenesisErrorNoMem;
+    long total = avio_size(avio);
+    if (total < 0)
+        total = -1;
+    long received = 0;
+    unsigned char buf[16384];
+    for (;;) {
+        int amt = avio_read(avio, buf, sizeof(buf));
+        if (amt == 0 || amt == AVERROR_EOF)
+            break;
+        if (amt < 0) {
+            err = download_error(amt);
+            break;
+        }
+        if (fwrite(buf, 1, amt, out_file) != (size_t)amt) {
+            err = GenesisErrorFileAccess;
+            break;
+        }
+        received += amt;
+        if (on_progress)
+            on_progress(userdata, received, total);
     }
 
-    audio_file->ic->interrupt_callback.callback = decode_interrupt_cb;
-    audio_file->ic->interrupt_callback.opaque = NULL;
+    avio_closep(&avio);
+    if (fclose(out_file) && !err)
+        err = GenesisErrorFileAccess;
+    return err;
+}
 
-    int av_err = avformat_open_input(&audio_file->ic, input_filename, NULL, NULL);
+// the built in decoder. decoded samples wait in pending until read_buffer
+// hands them out, because ffmpeg decides how many frames each packet holds.
+struct FfmpegDecoder {
+    AVFormatContext *ic;
+    AVCodecContext *codec_ctx;
+    AVFrame *in_frame;
+    int audio_stream_index;
+    int (*import_frame)(const AVFrame *, List<Channel> &);
+    List<Channel> pending;
+    int pending_offset;
+    bool end_of_packets;
+    bool flushed;
+};
+
+static void ffmpeg_decoder_close(struct GenesisAudioFileDecoder *decoder) {
+    FfmpegDecoder *fd = (FfmpegDecoder *)decoder->userdata;
+    av_frame_free(&fd->in_frame);
+    if (fd->codec_ctx) {
+        avcodec_close(fd->codec_ctx);
+        fd->codec_ctx = nullptr;
+    }
+    if (fd->ic)
+        avformat_close_input(&fd->ic);
+    fd->pending.clear();
+}
+
+static int ffmpeg_decoder_open(struct GenesisAudioFileDecoder *decoder, const char *input_filename,
+        struct GenesisAudioFile *audio_file)
+{
+    FfmpegDecoder *fd = (FfmpegDecoder *)decoder->userdata;
+    fd->pending_offset = 0;
+    fd->end_of_packets = false;
+    fd->flushed = false;
+
+    fd->ic = avformat_alloc_context();
+    if (!fd->ic)
+        return GenesisErrorNoMem;
+
+    fd->ic->interrupt_callback.callback = decode_interrupt_cb;
+    fd->ic->interrupt_callback.opaque = NULL;
+
+    int av_err = avformat_open_input(&fd->ic, input_filename, NULL, NULL);
     if (av_err < 0) {
-        genesis_audio_file_destroy(audio_file);
         if (av_err == AVERROR(ENOMEM)) {
             return GenesisErrorNoMem;
         } else if (av_err == AVERROR(EIO)) {
@@ -380,172 +463,247 @@ int genesis_audio_file_load(struct GenesisContext *context,
         }
     }
 
-    if ((av_err = avformat_find_stream_info(audio_file->ic, NULL)) < 0) {
-        genesis_audio_file_destroy(audio_file);
+    if ((av_err = avformat_find_stream_info(fd->ic, NULL)) < 0)
         return GenesisErrorDecodingAudio;
-    }
 
     // set all streams to discard. in a few lines here we will find the audio
     // stream and cancel discarding it
-    for (long i = 0; i < audio_file->ic->nb_streams; i += 1)
-        audio_file->ic->streams[i]->discard = AVDISCARD_ALL;
+    for (long i = 0; i < fd->ic->nb_streams; i += 1)
+        fd->ic->streams[i]->discard = AVDISCARD_ALL;
 
-    AVCodec *decoder = NULL;
-    int audio_stream_index = av_find_best_stream(audio_file->ic, AVMEDIA_TYPE_AUDIO, -1, -1, &decoder, 0);
-    if (audio_stream_index < 0) {
-        genesis_audio_file_destroy(audio_file);
+    AVCodec *codec = NULL;
+    fd->audio_stream_index = av_find_best_stream(fd->ic, AVMEDIA_TYPE_AUDIO, -1, -1, &codec, 0);
+    if (fd->audio_stream_index < 0)
         return GenesisErrorNoAudioFound;
-    }
-    if (!decoder) {
-        genesis_audio_file_destroy(audio_file);
+    if (!codec)
         return GenesisErrorNoDecoderFound;
-    }
 
-    AVStream *audio_st = audio_file->ic->streams[audio_stream_index];
+    AVStream *audio_st = fd->ic->streams[fd->audio_stream_index];
     audio_st->discard = AVDISCARD_DEFAULT;
 
-    audio_file->codec_ctx = audio_st->codec;
-    av_err = avcodec_open2(audio_file->codec_ctx, decoder, NULL);
-    if (av_err < 0) {
-        genesis_audio_file_destroy(audio_file);
+    av_err = avcodec_open2(audio_st->codec, codec, NULL);
+    if (av_err < 0)
         return GenesisErrorDecodingAudio;
-    }
+    fd->codec_ctx = audio_st->codec;
 
-    if (!audio_file->codec_ctx->channel_layout)
-        audio_file->codec_ctx->channel_layout = av_get_default_channel_layout(audio_file->codec_ctx->channels);
-    if (!audio_file->codec_ctx->channel_layout) {
-        genesis_audio_file_destroy(audio_file);
+    if (!fd->codec_ctx->channel_layout)
+        fd->codec_ctx->channel_layout = av_get_default_channel_layout(fd->codec_ctx->channels);
+    if (!fd->codec_ctx->channel_layout)
         return GenesisErrorNoAudioFound;
-    }
 
     // copy the audio stream metadata to the context metadata
-    av_dict_copy(&audio_file->ic->metadata, audio_st->metadata, 0);
+    av_dict_copy(&fd->ic->metadata, audio_st->metadata, 0);
 
     AVDictionaryEntry *tag = NULL;
-    audio_file->tags.clear();
-    while ((tag = av_dict_get(audio_file->ic->metadata, "", tag, AV_DICT_IGNORE_SUFFIX))) {
-        audio_file->tags.put(tag->key, tag->value);
-    }
+    while ((tag = av_dict_get(fd->ic->metadata, "", tag, AV_DICT_IGNORE_SUFFIX)))
+        genesis_audio_file_set_tag(audio_file, tag->key, -1, tag->value, -1);
 
-    int genesis_err = channel_layout_init_from_ffmpeg(audio_file->codec_ctx->channel_layout,
-           &audio_file->channel_layout);
-    if (genesis_err) {
-        genesis_audio_file_destroy(audio_file);
+    SoundIoChannelLayout channel_layout;
+    int genesis_err = channel_layout_init_from_ffmpeg(fd->codec_ctx->channel_layout, &channel_layout);
+    if (genesis_err)
+        return genesis_err;
+    if ((genesis_err = genesis_audio_file_set_channel_layout(audio_file, &channel_layout)))
         return genesis_err;
-    }
 
-    audio_file->sample_rate = audio_file->codec_ctx->sample_rate;
-    long channel_count = audio_file->channel_layout.channel_count;
+    genesis_audio_file_set_sample_rate(audio_file, fd->codec_ctx->sample_rate);
 
-    int (*import_frame)(const AVFrame *, GenesisAudioFile *);
-    switch (audio_file->codec_ctx->sample_fmt) {
+    switch (fd->codec_ctx->sample_fmt) {
         default:
             panic("unrecognized sample format");
             break;
         case AV_SAMPLE_FMT_U8:
-            import_frame = import_frame_uint8;
+            fd->import_frame = import_frame_uint8;
             break;
         case AV_SAMPLE_FMT_S16:
-            import_frame = import_frame_int16;
+            fd->import_frame = import_frame_int16;
             break;
         case AV_SAMPLE_FMT_S32:
-            import_frame = import_frame_int32;
+            fd->import_frame = import_frame_int32;
             break;
         case AV_SAMPLE_FMT_FLT:
-            import_frame = import_frame_float;
+            fd->import_frame = import_frame_float;
             break;
         case AV_SAMPLE_FMT_DBL:
-            import_frame = import_frame_double;
+            fd->import_frame = import_frame_double;
             break;
 
         case AV_SAMPLE_FMT_U8P:
-            import_frame = import_frame_uint8_planar;
+            fd->import_frame = import_frame_uint8_planar;
             break;
         case AV_SAMPLE_FMT_S16P:
-            import_frame = import_frame_int16_planar;
+            fd->import_frame = import_frame_int16_planar;
             break;
         case AV_SAMPLE_FMT_S32P:
-            import_frame = import_frame_int32_planar;
+            fd->import_frame = import_frame_int32_planar;
             break;
         case AV_SAMPLE_FMT_FLTP:
-            import_frame = import_frame_float_planar;
+            fd->import_frame = import_frame_float_planar;
             break;
         case AV_SAMPLE_FMT_DBLP:
-            import_frame = import_frame_double_planar;
+            fd->import_frame = import_frame_double_planar;
             break;
     }
 
-    if (audio_file->channels.resize(channel_count)) {
-        genesis_audio_file_destroy(audio_file);
+    if (fd->pending.resize(channel_layout.channel_count))
+        return GenesisErrorNoMem;
+    for (int i = 0; i < fd->pending.length(); i += 1)
+        fd->pending.at(i).samples.clear();
+
+    fd->in_frame = av_frame_alloc();
+    if (!fd->in_frame)
         return GenesisErrorNoMem;
+
+    return 0;
+}
+
+// decodes packets until there are pending frames or nothing is left
+static int ffmpeg_decoder_fill_pending(FfmpegDecoder *fd) {
+    while (fd->pending.at(0).samples.length() == 0 && !fd->flushed) {
+        AVPacket pkt;
+        memset(&pkt, 0, sizeof(AVPacket));
+        if (!fd->end_of_packets) {
+            int av_err = av_read_frame(fd->ic, &pkt);
+            if (av_err == AVERROR_EOF) {
+                fd->end_of_packets = true;
+                continue;
+            } else if (av_err < 0) {
+                return GenesisErrorDecodingAudio;
+            }
+            int negative_err = decode_frame(fd->pending, &pkt, fd->codec_ctx, fd->in_frame, fd->import_frame);
+            av_packet_unref(&pkt);
+            // ignore decoding errors and try the next frame
+            if (negative_err < 0 && negative_err != -GenesisErrorDecodingAudio)
+                return -negative_err;
+        } else {
+            // flush
+            av_init_packet(&pkt);
+            pkt.data = NULL;
+            pkt.size = 0;
+            pkt.stream_index = fd->audio_stream_index;
+            int negative_err = decode_frame(fd->pending, &pkt, fd->codec_ctx, fd->in_frame, fd->import_frame);
+            // treat decoding errors as EOFs
+            if (negative_err == -GenesisErrorDecodingAudio || negative_err == 0)
+                fd->flushed = true;
+            else if (negative_err < 0)
+                return -negative_err;
+        }
     }
-    for (int i = 0; i < audio_file->channels.length(); i += 1) {
-        audio_file->channels.at(i).samples.clear();
+    return 0;
+}
+
+static int ffmpeg_decoder_read_buffer(struct GenesisAudioFileDecoder *decoder, float **channel_buffers,
+        int max_frame_count, int *out_frame_count)
+{
+    FfmpegDecoder *fd = (FfmpegDecoder *)decoder->userdata;
+    *out_frame_count = 0;
+    if (fd->pending_offset >= fd->pending.at(0).samples.length()) {
+        for (int ch = 0; ch < fd->pending.length(); ch += 1)
+            fd->pending.at(ch).samples.clear();
+        fd->pending_offset = 0;
+        int err = ffmpeg_decoder_fill_pending(fd);
+        if (err)
+            return err;
     }
 
-    audio_file->in_frame = av_frame_alloc();
-    if (!audio_file->in_frame) {
-        genesis_audio_file_destroy(audio_file);
-        return GenesisErrorNoMem;
+    int frame_count = min(max_frame_count, fd->pending.at(0).samples.length() - fd->pending_offset);
+    for (int ch = 0; ch < fd->pending.length(); ch += 1) {
+        memcpy(channel_buffers[ch], fd->pending.at(ch).samples.raw() + fd->pending_offset,
+                frame_count * sizeof(float));
     }
+    fd->pending_offset += frame_count;
+    *out_frame_count = frame_count;
+    return 0;
+}
 
-    AVPacket pkt;
-    memset(&pkt, 0, sizeof(AVPacket));
+static double ffmpeg_decoder_duration(struct GenesisAudioFileDecoder *decoder) {
+    FfmpegDecoder *fd = (FfmpegDecoder *)decoder->userdata;
+    return (fd->ic->duration == AV_NOPTS_VALUE) ? -1.0 : fd->ic->duration / (double)AV_TIME_BASE;
+}
+
+static GenesisAudioFileDecoder ffmpeg_decoder(FfmpegDecoder *fd) {
+    return {
+        fd,
+        ffmpeg_decoder_open,
+        ffmpeg_decoder_read_buffer,
+        ffmpeg_decoder_duration,
+        ffmpeg_decoder_close,
+    };
+}
+
+// frames asked of the decoder at a time
+static const int decode_buffer_frame_count = 4096;
+
+static int load_with_decoder(GenesisAudioFileDecoder *decoder, GenesisAudioFile *audio_file,
+        const char *input_filename)
+{
+    int err;
+    if ((err = decoder->open(decoder, input_filename, audio_file)))
+        return err;
+
+    audio_file->container_duration = decoder->duration(decoder);
+    int channel_count = audio_file->channels.length();
+    List<float *> channel_buffers;
+    if ((err = channel_buffers.resize(channel_count)))
+        return err;
+    for (int ch = 0; ch < channel_count; ch += 1)
+        audio_file->channels.at(ch).samples.clear();
 
     for (;;) {
-        av_err = av_read_frame(audio_file->ic, &pkt);
-        if (av_err == AVERROR_EOF) {
-            break;
-        } else if (av_err < 0) {
-            genesis_audio_file_destroy(audio_file);
-            return GenesisErrorDecodingAudio;
-        }
-        int negative_err = decode_frame(audio_file, &pkt, audio_file->codec_ctx, audio_file->in_frame, import_frame);
-        av_packet_unref(&pkt);
-        if (negative_err == -GenesisErrorDecodingAudio) {
-            // ignore decoding errors and try the next frame
-            continue;
-        } else if (negative_err < 0) {
-            genesis_audio_file_destroy(audio_file);
-            return -negative_err;
+        int length = audio_file->channels.at(0).samples.length();
+        for (int ch = 0; ch < channel_count; ch += 1) {
+            List<float> &samples = audio_file->channels.at(ch).samples;
+            if ((err = samples.resize(length + decode_buffer_frame_count)))
+                return err;
+            channel_buffers.at(ch) = samples.raw() + length;
         }
+        int frame_count;
+        err = decoder->read_buffer(decoder, channel_buffers.raw(), decode_buffer_frame_count, &frame_count);
+        for (int ch = 0; ch < channel_count; ch += 1)
+            ok_or_panic(audio_file->channels.at(ch).samples.resize(length + (err ? 0 : frame_count)));
+        if (err)
+            return err;
+        if (frame_count == 0)
+            return 0;
     }
+}
 
-    // flush
-    for (;;) {
-        av_init_packet(&pkt);
-        pkt.data = NULL;
-        pkt.size = 0;
-        pkt.stream_index = audio_stream_index;
-        int negative_err = decode_frame(audio_file, &pkt, audio_file->codec_ctx, audio_file->in_frame, import_frame);
-        if (negative_err == -GenesisErrorDecodingAudio) {
-            // treat decoding errors as EOFs
-            break;
-        } else if (negative_err < 0) {
+int genesis_audio_file_load(struct GenesisContext *context,
+        const char *input_filename, struct GenesisAudioFile **out_audio_file)
+{
+    *out_audio_file = nullptr;
+    GenesisAudioFile *audio_file = genesis_audio_file_create(context, 0);
+    if (!audio_file)
+        return GenesisErrorNoMem;
+
+    // each load gets its own ffmpeg state so that files can load in parallel
+    FfmpegDecoder *fd = nullptr;
+    GenesisAudioFileDecoder default_decoder;
+    GenesisAudioFileDecoder *decoder = context->audio_file_decoder;
+    if (!decoder) {
+        fd = create_zero<FfmpegDecoder>();
+        if (!fd) {
             genesis_audio_file_destroy(audio_file);
-            return -negative_err;
-        } else if (negative_err == 0) {
-            break;
+            return GenesisErrorNoMem;
         }
+        default_decoder = ffmpeg_decoder(fd);
+        decoder = &default_decoder;
     }
 
-    av_frame_free(&audio_file->in_frame); audio_file->in_frame = nullptr;
-    avcodec_close(audio_file->codec_ctx); audio_file->codec_ctx = nullptr;
-    avformat_close_input(&audio_file->ic); audio_file->ic = nullptr;
+    int err = load_with_decoder(decoder, audio_file, input_filename);
+    decoder->close(decoder);
+    destroy(fd, 1);
+    if (err) {
+        genesis_audio_file_destroy(audio_file);
+        return err;
+    }
 
     *out_audio_file = audio_file;
     return 0;
 }
 
 void genesis_audio_file_destroy(struct GenesisAudioFile *audio_file) {
-    if (audio_file) {
-        av_frame_free(&audio_file->in_frame);
-        if (audio_file->codec_ctx)
-            avcodec_close(audio_file->codec_ctx);
-        if (audio_file->ic)
-            avformat_close_input(&audio_file->ic);
+    if (audio_file)
         destroy(audio_file, 1);
-    }
 }
 
 GenesisAudioFileCodec *audio_file_guess_audio_file_codec(
@@ -960,6 +1118,9 @@ int audio_file_init(void) {
     av_log_set_level(AV_LOG_QUIET);
     avcodec_register_all();
     av_register_all();
+    // for http:// and https:// urls
+    if (avformat_network_init() < 0)
+        return GenesisErrorSystemResources;
     return 0;
 }
 
@@ -1109,6 +1270,87 @@ int genesis_audio_file_sample_rate(const struct GenesisAudioFile *audio_file) {
     return audio_file->sample_rate;
 }
 
+static bool tag_key_equal(const ByteBuffer &key, const char *other) {
+    for (int i = 0; i < key.length(); i += 1) {
+        if (!other[i] || tolower((unsigned char)key.at(i)) != tolower((unsigned char)other[i]))
+            return false;
+    }
+    return other[key.length()] == 0;
+}
+
+const char *genesis_audio_file_tag(const struct GenesisAudioFile *audio_file, const char *key) {
+    auto it = audio_file->tags.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            return nullptr;
+        if (tag_key_equal(entry->key, key))
+            return entry->value.raw();
+    }
+}
+
+double genesis_audio_file_duration(const struct GenesisAudioFile *audio_file) {
+    return genesis_audio_file_frame_count(audio_file) / (double)audio_file->sample_rate;
+}
+
+double genesis_audio_file_container_duration(const struct GenesisAudioFile *audio_file) {
+    return audio_file->container_duration;
+}
+
+static float frame_sum(const struct GenesisAudioFile *audio_file, long frame) {
+    float sum = 0.0f;
+    for (int ch = 0; ch < audio_file->channels.length(); ch += 1)
+        sum += audio_file->channels.at(ch).samples.at(frame);
+    return sum;
+}
+
+// whether the signal crosses zero between frame - 1 and frame
+static bool is_zero_crossing(const struct GenesisAudioFile *audio_file, long frame) {
+    if (frame < 1 || frame >= genesis_audio_file_frame_count(audio_file))
+        return false;
+    float prev = frame_sum(audio_file, frame - 1);
+    float cur = frame_sum(audio_file, frame);
+    return cur == 0.0f || (prev < 0.0f) != (cur < 0.0f);
+}
+
+long genesis_audio_file_find_zero_crossing(const struct GenesisAudioFile *audio_file,
+        long frame, long max_distance)
+{
+    for (long distance = 0; distance <= max_distance; distance += 1) {
+        if (is_zero_crossing(audio_file, frame - distance))
+            return frame - distance;
+        if (distance > 0 && is_zero_crossing(audio_file, frame + distance))
+            return frame + distance;
+    }
+    return frame;
+}
+
+int genesis_audio_file_stereo_correlation(const struct GenesisAudioFile *audio_file,
+        long start_frame, long end_frame, double *out_correlation)
+{
+    if (audio_file->channels.length() != 2)
+        return GenesisErrorIncompatibleChannelLayouts;
+    long frame_count = genesis_audio_file_frame_count(audio_file);
+    start_frame = clamp(0L, start_frame, frame_count);
+    end_frame = clamp(start_frame, end_frame, frame_count);
+
+    const List<float> &left = audio_file->channels.at(0).samples;
+    const List<float> &right = audio_file->channels.at(1).samples;
+    double sum_lr = 0.0;
+    double sum_ll = 0.0;
+    double sum_rr = 0.0;
+    for (long frame = start_frame; frame < end_frame; frame += 1) {
+        double l = left.at(frame);
+        double r = right.at(frame);
+        sum_lr += l * r;
+        sum_ll += l * l;
+        sum_rr += r * r;
+    }
+    double denominator = sqrt(sum_ll * sum_rr);
+    *out_correlation = (denominator > 0.0) ? clamp(-1.0, sum_lr / denominator, 1.0) : 0.0;
+    return 0;
+}
+
 struct GenesisAudioFileIterator genesis_audio_file_iterator(
         struct GenesisAudioFile *audio_file, int channel_index, long start_frame_index)
 {
@@ -1184,6 +1426,7 @@ struct GenesisAudioFile *genesis_audio_file_create(struct GenesisContext *contex
     }
 
     audio_file->sample_rate = sample_rate;
+    audio_file->container_duration = -1.0;
     audio_file->channel_layout = *soundio_channel_layout_get_builtin(SoundIoChannelLayoutIdMono);
     if (audio_file->channels.resize(1)) {
         genesis_audio_file_destroy(audio_file);
@@ -1202,7 +1445,7 @@ void genesis_audio_file_set_sample_rate(struct GenesisAudioFile *audio_file,
 int genesis_audio_file_set_channel_layout(struct GenesisAudioFile *audio_file,
         const SoundIoChannelLayout *channel_layout)
 {
-    int err = audio_file->channels.resize(audio_file->channel_layout.channel_count);
+    int err = audio_file->channels.resize(channel_layout->channel_count);
     if (err)
         return err;
     audio_file->channel_layout = *channel_layout;
@@ -1313,6 +1556,14 @@ void genesis_audio_file_stream_set_channel_layout(struct GenesisAudioFileStream
     afs->channel_layout = *channel_layout;
 }
 
+void genesis_audio_file_set_tag(struct GenesisAudioFile *audio_file,
+        const char *tag_key, int tag_key_len, const char *tag_value, int tag_value_len)
+{
+    ByteBuffer key(tag_key, tag_key_len);
+    ByteBuffer value(tag_value, tag_value_len);
+    audio_file->tags.put(key, value);
+}
+
 void genesis_audio_file_stream_set_tag(struct GenesisAudioFileStream *afs,
         const char *tag_key, int tag_key_len, const char *tag_value, int tag_value_len)
 {
@@ -16,9 +16,8 @@ struct GenesisAudioFile {
     SoundIoChannelLayout channel_layout;
     int sample_rate;
     HashMap<ByteBuffer, ByteBuffer, ByteBuffer::hash> tags;
-    AVFormatContext *ic;
-    AVCodecContext *codec_ctx;
-    AVFrame *in_frame;
+    // seconds, as reported by the container. -1 if it did not say
+    double container_duration;
     GenesisContext *genesis_context;
 };
 
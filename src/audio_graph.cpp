@@ -1019,6 +1019,12 @@ void audio_graph_flush_events(AudioGraph *ag) {
     if ((!ag->render_stream && ag->is_playing) || !ag->play_head_changed_flag.test_and_set()) {
         ag->events.trigger(EventAudioGraphPlayHeadChanged);
     }
+
+    // taking the levels resets them, so this is their only reader
+    if (ag->mixer_node)
+        mixer_node_take_levels(ag->mixer_node, &ag->levels);
+    else
+        ag->levels.channel_count = 0;
 }
 
 double audio_graph_play_head_pos(AudioGraph *ag) {
@@ -8,6 +8,7 @@
 #include "midi_hardware.hpp"
 #include "settings_file.hpp"
 #include "event_dispatcher.hpp"
+#include "mixer_node.hpp"
 
 struct EventList {
     List<GenesisMidiEvent> events;
@@ -64,6 +65,10 @@ struct AudioGraph {
     double play_head_pos;
     atomic_bool is_playing;
     atomic_flag play_head_changed_flag;
+
+    // the levels of the mix since the previous audio_graph_flush_events,
+    // for every level meter to read. see mixer_node_take_levels
+    ChannelLevels levels;
 };
 
 int audio_graph_create_playback(Project *project, GenesisContext *genesis_context,
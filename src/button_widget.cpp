@@ -50,10 +50,12 @@ void ButtonWidget::on_mouse_move(const MouseEvent *event) {
 
 void ButtonWidget::on_mouse_out(const MouseEvent *event) {
     hovering = false;
+    gui_window->set_cursor_default();
 }
 
 void ButtonWidget::on_mouse_over(const MouseEvent *event) {
     hovering = true;
+    gui_window->set_cursor_hand();
 }
 
 void ButtonWidget::set_text(const String &text) {
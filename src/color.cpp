@@ -0,0 +1,23 @@
+#include "color.hpp"
+
+#include <math.h>
+
+bool color_high_contrast = false;
+
+static float linear_channel(float c) {
+    return (c <= 0.03928f) ? (c / 12.92f) : powf((c + 0.055f) / 1.055f, 2.4f);
+}
+
+static float relative_luminance(const glm::vec4 &color) {
+    return 0.2126f * linear_channel(color[0]) +
+        0.7152f * linear_channel(color[1]) +
+        0.0722f * linear_channel(color[2]);
+}
+
+float color_contrast_ratio(const glm::vec4 &a, const glm::vec4 &b) {
+    float la = relative_luminance(a);
+    float lb = relative_luminance(b);
+    float lighter = (la > lb) ? la : lb;
+    float darker = (la > lb) ? lb : la;
+    return (lighter + 0.05f) / (darker + 0.05f);
+}
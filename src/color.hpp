@@ -2,8 +2,18 @@
 #define COLOR_HPP
 
 #include "glm.hpp"
+#include "util.hpp"
 
 #include <stdio.h>
+#include <string.h>
+
+// switches every color below to a variant with stronger contrast between
+// text, backgrounds and borders. see Gui::set_high_contrast
+extern bool color_high_contrast;
+
+// WCAG contrast ratio between two opaque colors, from 1 for identical
+// luminance up to 21 for black on white
+float color_contrast_ratio(const glm::vec4 &a, const glm::vec4 &b);
 
 static glm::vec4 parse_color(const char *color) {
     if (color[0] == '#')
@@ -30,51 +40,87 @@ static glm::vec4 parse_color(const char *color) {
 }
 
 static inline glm::vec4 color_fg_text(void) {
-    return parse_color("#DCDCDC");
+    return parse_color(color_high_contrast ? "#FFFFFF" : "#DCDCDC");
 }
 
 static inline glm::vec4 color_light_disabled_text(void) {
-    return parse_color("#C7C7C7");
+    return parse_color(color_high_contrast ? "#BFBFBF" : "#C7C7C7");
 }
 
 static inline glm::vec4 color_light_bg(void) {
-    return parse_color("#949494");
+    return parse_color(color_high_contrast ? "#C8C8C8" : "#949494");
 }
 
 static inline glm::vec4 color_dark_bg(void) {
-    return parse_color("#333333");
+    return parse_color(color_high_contrast ? "#000000" : "#333333");
 }
 
 static inline glm::vec4 color_dark_bg_highlight(void) {
-    return parse_color("#404040");
+    return parse_color(color_high_contrast ? "#2A2A2A" : "#404040");
 }
 
 static inline glm::vec4 color_dark_bg_alt(void) {
-    return parse_color("#2E5986");
+    return parse_color(color_high_contrast ? "#003D7A" : "#2E5986");
 }
 
 static inline glm::vec4 color_dark_text(void) {
-    return parse_color("#232323");
+    return parse_color(color_high_contrast ? "#000000" : "#232323");
 }
 
 static inline glm::vec4 color_dark_border(void) {
-    return parse_color("#1B1B1B");
+    return parse_color(color_high_contrast ? "#8C8C8C" : "#1B1B1B");
 }
 
 static inline glm::vec4 color_light_border(void) {
-    return parse_color("#868686");
+    return parse_color(color_high_contrast ? "#FFFFFF" : "#868686");
 }
 
 static inline glm::vec4 color_dark_bg_inactive(void) {
-    return parse_color("#777777");
+    return parse_color(color_high_contrast ? "#4D4D4D" : "#777777");
 }
 
 static inline glm::vec4 color_attention_overlay(void) {
-    return parse_color("#D82B2B");
+    return parse_color(color_high_contrast ? "#FF3B3B" : "#D82B2B");
+}
+
+static inline glm::vec4 color_grid_line(void) {
+    return parse_color(color_high_contrast ? "#FFFFFF40" : "#FFFFFF14");
+}
+
+static inline glm::vec4 color_modal_backdrop(void) {
+    return parse_color(color_high_contrast ? "#000000C0" : "#00000080");
+}
+
+static inline glm::vec4 color_focus_ring(void) {
+    return parse_color(color_high_contrast ? "#FFD400" : "#4FC1E9");
 }
 
 static inline glm::vec4 color_selection(void) {
-    return parse_color("#254385");
+    return parse_color(color_high_contrast ? "#0042A8" : "#254385");
+}
+
+static inline glm::vec4 color_layout_guide(void) {
+    return parse_color(color_high_contrast ? "#FF00FF" : "#E040E0");
+}
+
+static inline glm::vec4 color_meter_rms(void) {
+    return parse_color(color_high_contrast ? "#00E000" : "#3FAE49");
+}
+
+static inline glm::vec4 color_meter_peak(void) {
+    return parse_color(color_high_contrast ? "#00E00080" : "#3FAE4966");
+}
+
+static inline glm::vec4 color_meter_hold(void) {
+    return parse_color(color_high_contrast ? "#FFD400" : "#E8C547");
+}
+
+static inline glm::vec4 color_meter_in_phase(void) {
+    return parse_color(color_high_contrast ? "#00E000" : "#3FAE49");
+}
+
+static inline glm::vec4 color_meter_out_of_phase(void) {
+    return parse_color(color_high_contrast ? "#FF3030" : "#D9534F");
 }
 
 #endif
@@ -0,0 +1,90 @@
+#include "correlation_meter_widget.hpp"
+#include "gui_window.hpp"
+#include "gui.hpp"
+#include "label.hpp"
+#include "color.hpp"
+
+static void on_frame_update(Event, void *userdata) {
+    CorrelationMeterWidget *correlation_meter_widget = (CorrelationMeterWidget *)userdata;
+    correlation_meter_widget->frame_update();
+}
+
+CorrelationMeterWidget::CorrelationMeterWidget(GuiWindow *gui_window, AudioGraph *audio_graph) :
+    Widget(gui_window),
+    window_seconds(0.3),
+    audio_graph(audio_graph),
+    bg_color(color_dark_bg()),
+    text_color(color_fg_text()),
+    in_phase_color(color_meter_in_phase()),
+    out_of_phase_color(color_meter_out_of_phase()),
+    center_color(color_light_bg()),
+    padding(4),
+    bar_width(48),
+    label(create<Label>(gui)),
+    stereo(false),
+    correlation(0.0)
+{
+    // wide enough for any reading so that the width does not follow the text
+    label->set_text("-1.00");
+    label->update();
+    text_width = label->width();
+
+    update_label();
+    gui->events.attach_handler(EventFrameUpdate, on_frame_update, this);
+}
+
+CorrelationMeterWidget::~CorrelationMeterWidget() {
+    gui->events.detach_handler(EventFrameUpdate, on_frame_update);
+    destroy(label, 1);
+}
+
+int CorrelationMeterWidget::min_width() const {
+    return padding + text_width + padding + bar_width + padding;
+}
+
+int CorrelationMeterWidget::max_width() const {
+    return min_width();
+}
+
+void CorrelationMeterWidget::update_label() {
+    ByteBuffer text;
+    if (stereo)
+        text.format("%+.2f", correlation);
+    else
+        text = "N/A";
+    String new_text = text;
+    if (String::compare(new_text, label->text()) == 0)
+        return;
+    label->set_text(new_text);
+    label->update();
+}
+
+void CorrelationMeterWidget::frame_update() {
+    double play_head_pos = audio_graph_play_head_pos(audio_graph);
+    stereo = project_stereo_correlation(audio_graph->project, play_head_pos,
+            window_seconds, &correlation);
+    update_label();
+
+    if (stereo && audio_graph_is_playing(audio_graph))
+        gui->keep_animating();
+}
+
+void CorrelationMeterWidget::draw(const glm::mat4 &projection) {
+    gui_window->fill_rect(bg_color, left, top, width, height);
+
+    int label_left = padding + text_width - label->width();
+    int label_top = height / 2 - label->height() / 2;
+    label->draw(projection * transform2d(label_left, label_top), text_color);
+
+    int bar_left = left + width - padding - bar_width;
+    int bar_height = height - padding * 2;
+    int center = bar_left + bar_width / 2;
+    if (stereo) {
+        int extent = (int)(correlation * (bar_width / 2));
+        if (extent > 0)
+            gui_window->fill_rect(in_phase_color, center, top + padding, extent, bar_height);
+        else if (extent < 0)
+            gui_window->fill_rect(out_of_phase_color, center + extent, top + padding, -extent, bar_height);
+    }
+    gui_window->fill_rect(center_color, center, top + padding, 1, bar_height);
+}
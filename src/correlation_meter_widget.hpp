@@ -0,0 +1,51 @@
+#ifndef CORRELATION_METER_WIDGET_HPP
+#define CORRELATION_METER_WIDGET_HPP
+
+#include "widget.hpp"
+#include "audio_graph.hpp"
+
+class Label;
+
+// how alike the left and right channels of the audio under the play head
+// are, as a number and a bar growing from the middle: right towards 1 when
+// they are the same, left towards -1 when one is the other inverted, which
+// cancels out when summed to mono. shows N/A unless the audio is stereo.
+// see project_stereo_correlation
+class CorrelationMeterWidget : public Widget {
+public:
+    CorrelationMeterWidget(GuiWindow *gui_window, AudioGraph *audio_graph);
+    ~CorrelationMeterWidget() override;
+
+    void draw(const glm::mat4 &projection) override;
+
+    int min_width() const override;
+    int max_width() const override;
+
+    // how many seconds of audio up to the play head the reading covers.
+    // default 0.3
+    double window_seconds;
+
+    AudioGraph *audio_graph;
+
+    void frame_update();
+
+private:
+    glm::vec4 bg_color;
+    glm::vec4 text_color;
+    glm::vec4 in_phase_color;
+    glm::vec4 out_of_phase_color;
+    glm::vec4 center_color;
+
+    int padding;
+    int bar_width;
+    int text_width;
+
+    Label *label;
+
+    bool stereo;
+    double correlation;
+
+    void update_label();
+};
+
+#endif
@@ -48,7 +48,7 @@ void DockAreaWidget::draw(const glm::mat4 &projection) {
     switch (layout) {
         case DockAreaLayoutTabs:
             if (tab_widget) {
-                tab_widget->draw(projection);
+                tab_widget->draw_clipped(projection);
 
                 if (insert_tab_arrow) {
                     gui->draw_image_color(gui_window, gui->img_arrow_down,
@@ -73,8 +73,8 @@ void DockAreaWidget::draw(const glm::mat4 &projection) {
         case DockAreaLayoutVert:
             assert(child_a);
             assert(child_b);
-            child_a->draw(projection);
-            child_b->draw(projection);
+            child_a->draw_clipped(projection);
+            child_b->draw_clipped(projection);
             gui_window->fill_rect(light_border_color, projection * split_border_start_model);
             gui_window->fill_rect(dark_border_color, projection * split_border_end_model);
             break;
@@ -648,7 +648,7 @@ DockablePaneWidget::DockablePaneWidget(Widget *child, const String &title) :
 }
 
 void DockablePaneWidget::draw(const glm::mat4 &projection) {
-    child->draw(projection);
+    child->draw_clipped(projection);
 }
 
 void DockablePaneWidget::on_drag(const DragEvent *event) {
@@ -11,9 +11,14 @@ enum Event {
     EventWindowClose,
     EventWindowPosChange,
     EventWindowSizeChange,
+    EventWindowFilesDropped,
     EventAudioDeviceChange,
     EventMidiDeviceChange,
     EventFlushEvents,
+    // once per frame, before the windows draw. see Gui::delta_time
+    EventFrameUpdate,
+    // zero or more times per frame, Gui::fixed_timestep apart
+    EventFixedUpdate,
     EventPerspectiveChange,
     EventScrollValueChange,
     EventProjectAudioAssetsChanged,
@@ -31,6 +36,7 @@ enum Event {
     EventSettingsDefaultRenderFormatChanged,
     EventSettingsDefaultRenderSampleFormatChanged,
     EventSettingsDefaultRenderBitRateChanged,
+    EventSettingsMarkersChanged,
     EventRenderJobsUpdated,
     EventAudioGraphPlayHeadChanged,
     EventAudioGraphPlayingChanged,
@@ -0,0 +1,30 @@
+#ifndef FIXED_TIMESTEP_HPP
+#define FIXED_TIMESTEP_HPP
+
+// counts the fixed steps that fit in the time that passed each frame. what
+// is left over carries into the next frame, so the steps taken over a
+// stretch of time do not depend on how the frames divided it up.
+class FixedTimestep {
+public:
+    FixedTimestep() :
+        _accumulator(0.0)
+    {
+    }
+
+    // how many steps of timestep seconds fit in delta_time plus what was
+    // left over from before
+    int advance(double timestep, double delta_time) {
+        _accumulator += delta_time;
+        int step_count = 0;
+        while (_accumulator >= timestep) {
+            _accumulator -= timestep;
+            step_count += 1;
+        }
+        return step_count;
+    }
+
+private:
+    double _accumulator;
+};
+
+#endif
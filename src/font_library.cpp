@@ -0,0 +1,130 @@
+#include "font_library.hpp"
+
+uint32_t hash_int(const int &x) {
+    return (uint32_t) x;
+}
+
+static void ft_ok(FT_Error err) {
+    if (err)
+        panic("freetype error");
+}
+
+FontLibrary::FontLibrary(const ByteBuffer &font_buffer) :
+    notdef_style(NotdefStyleFontDefault),
+    _default_font_buffer(font_buffer),
+    _font_generation(0)
+{
+    ft_ok(FT_Init_FreeType(&_ft_library));
+    ft_ok(FT_New_Memory_Face(_ft_library, (FT_Byte*)_default_font_buffer.raw(),
+                _default_font_buffer.length(), 0, &_default_font_face));
+}
+
+FontLibrary::~FontLibrary() {
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        FontSize *font_size_object = entry->value;
+        destroy(font_size_object, 1);
+    }
+
+    FT_Done_Face(_default_font_face);
+    FT_Done_FreeType(_ft_library);
+}
+
+FontSize *FontLibrary::get_font_size(int font_size) {
+    auto *entry = _font_size_cache.maybe_get(font_size);
+    if (entry)
+        return entry->value;
+    FontSize *font_size_object = create<FontSize>(_default_font_face, font_size);
+    font_size_object->set_notdef_style(notdef_style);
+    _font_size_cache.put(font_size, font_size_object);
+    return font_size_object;
+}
+
+int FontLibrary::reload_font(const char *path) {
+    FT_Face new_face;
+    if (FT_New_Face(_ft_library, path, 0, &new_face))
+        return GenesisErrorFileAccess;
+
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        FontSize *font_size = entry->value;
+        if (font_size->font_face() == _default_font_face)
+            font_size->set_font_face(new_face);
+    }
+
+    FT_Done_Face(_default_font_face);
+    _default_font_face = new_face;
+    _default_font_buffer.clear();
+    _font_generation += 1;
+    return 0;
+}
+
+void FontLibrary::clear_cache() {
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        entry->value->clear_cache();
+    }
+    _font_generation += 1;
+}
+
+void FontLibrary::set_notdef_style(NotdefStyle style) {
+    if (style == notdef_style)
+        return;
+    notdef_style = style;
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        entry->value->set_notdef_style(style);
+    }
+    _font_generation += 1;
+}
+
+int FontLibrary::glyph_cache_count() {
+    int count = 0;
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        count += entry->value->glyph_count();
+    }
+    return count;
+}
+
+void FontLibrary::layout_cache_stats(int *hits, int *misses) {
+    *hits = 0;
+    *misses = 0;
+    auto it = _font_size_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        *hits += entry->value->layout_cache_hits;
+        *misses += entry->value->layout_cache_misses;
+    }
+}
+
+bool FontLibrary::has_glyph(uint32_t codepoint) {
+    return FT_Get_Char_Index(_default_font_face, codepoint) != 0;
+}
+
+void FontLibrary::get_charset(List<uint32_t> &out_codepoints) {
+    out_codepoints.clear();
+    FT_UInt glyph_index;
+    FT_ULong codepoint = FT_Get_First_Char(_default_font_face, &glyph_index);
+    while (glyph_index != 0) {
+        ok_or_panic(out_codepoints.append(codepoint));
+        codepoint = FT_Get_Next_Char(_default_font_face, codepoint, &glyph_index);
+    }
+}
@@ -0,0 +1,63 @@
+#ifndef FONT_LIBRARY_HPP
+#define FONT_LIBRARY_HPP
+
+#include "font_size.hpp"
+#include "freetype.hpp"
+#include "hash_map.hpp"
+
+uint32_t hash_int(const int &x);
+
+// the font faces and everything measured from them: glyph bitmaps, advances,
+// kerning and cached layouts. none of it touches OpenGL, so one FontLibrary
+// can serve any number of Gui instances and windows, or code that lays text
+// out without a window at all.
+//
+// what does depend on a context stays with whoever draws: each Label uploads
+// its own texture, and Gui owns the shader programs. the windows of one Gui
+// share a context with its utility window, so their textures are shared too.
+class FontLibrary {
+public:
+    // font_buffer holds a font file, which is copied
+    FontLibrary(const ByteBuffer &font_buffer);
+    ~FontLibrary();
+
+    FontSize *get_font_size(int font_size);
+    // replaces the default face with the font file at path, for trying out
+    // fonts without restarting, and evicts everything cached from the old
+    // face. labels pick it up on their next update().
+    int reload_font(const char *path);
+    // evicts every cached glyph, layout and measurement of every face, so
+    // that nothing measured before carries over. labels pick it up on their
+    // next update().
+    void clear_cache();
+    // number of glyphs cached across all font sizes
+    int glyph_cache_count();
+    // summed over every font size
+    void layout_cache_stats(int *hits, int *misses);
+
+    // whether the font has a glyph for codepoint
+    bool has_glyph(uint32_t codepoint);
+    // how every font size draws characters that has_glyph() is false for.
+    // labels pick it up on their next update(). default NotdefStyleFontDefault
+    void set_notdef_style(NotdefStyle notdef_style);
+    NotdefStyle notdef_style;
+    // every codepoint the font has a glyph for, in ascending order
+    void get_charset(List<uint32_t> &out_codepoints);
+
+    FT_Library _ft_library;
+    FT_Face _default_font_face;
+
+    // key is font size
+    HashMap<int, FontSize *, hash_int> _font_size_cache;
+
+    ByteBuffer _default_font_buffer;
+    // incremented whenever cached glyphs change, so labels know to redo
+    // their layout
+    int _font_generation;
+
+private:
+    FontLibrary(const FontLibrary &copy) = delete;
+    FontLibrary &operator=(const FontLibrary &copy) = delete;
+};
+
+#endif
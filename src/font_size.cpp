@@ -1,16 +1,53 @@
 #include "font_size.hpp"
 
+#include FT_BITMAP_H
+
 static void ft_ok(FT_Error err) {
     if (err)
         panic("freetype error");
 }
 
 FontSize::FontSize(FT_Face font_face, int font_size) :
+    layout_cache_hits(0),
+    layout_cache_misses(0),
     _max_above_size(0),
     _max_below_size(0),
+    _layout_use_count(0),
+    _metrics_use_count(0),
     _font_face(font_face),
-    _font_size(font_size)
+    _font_size(font_size),
+    _notdef_style(NotdefStyleFontDefault)
 {
+    prefill_cache();
+}
+
+FontSize::~FontSize() {
+    clear_glyph_cache();
+    clear_layout_cache();
+}
+
+void FontSize::set_font_face(FT_Face font_face) {
+    _font_face = font_face;
+    clear_cache();
+}
+
+void FontSize::clear_cache() {
+    clear_glyph_cache();
+    clear_layout_cache();
+    _metrics_cache.clear();
+    _max_above_size = 0;
+    _max_below_size = 0;
+    prefill_cache();
+}
+
+void FontSize::set_notdef_style(NotdefStyle notdef_style) {
+    if (notdef_style == _notdef_style)
+        return;
+    _notdef_style = notdef_style;
+    clear_cache();
+}
+
+void FontSize::prefill_cache() {
     // pre-fill some characters in the cache so that we have a good measurement of
     // _max_above_size and _max_below_size
     static const char * some_characters =
@@ -19,7 +56,7 @@ FontSize::FontSize(FT_Face font_face, int font_size) :
         font_cache_entry((uint32_t)*ptr);
 }
 
-FontSize::~FontSize() {
+void FontSize::clear_glyph_cache() {
     auto it = _font_cache.entry_iterator();
     for (;;) {
         auto *entry = it.next();
@@ -28,6 +65,125 @@ FontSize::~FontSize() {
         FontCacheValue *font_cache_value = &entry->value;
         FT_Done_Glyph(font_cache_value->glyph);
     }
+    _font_cache.clear();
+}
+
+void FontSize::clear_layout_cache() {
+    auto it = _layout_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        destroy(entry->value, 1);
+    }
+    _layout_cache.clear();
+}
+
+void FontSize::evict_least_recent_layout() {
+    HashMap<ByteBuffer, TextLayout *, ByteBuffer::hash>::Entry *oldest = nullptr;
+    auto it = _layout_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        if (!oldest || entry->value->last_use < oldest->value->last_use)
+            oldest = entry;
+    }
+    if (!oldest)
+        return;
+    ByteBuffer key = oldest->key;
+    destroy(oldest->value, 1);
+    _layout_cache.remove(key);
+}
+
+const TextLayout *FontSize::get_layout(const ByteBuffer &text) {
+    auto *entry = _layout_cache.maybe_get(text);
+    if (!entry) {
+        layout_cache_misses += 1;
+        return nullptr;
+    }
+    layout_cache_hits += 1;
+    entry->value->last_use = ++_layout_use_count;
+    return entry->value;
+}
+
+void FontSize::put_layout(const ByteBuffer &text, const List<TextLayoutLetter> &letters, int width) {
+    if (_layout_cache.maybe_get(text))
+        return;
+    if (_layout_cache.size() >= max_cached_layouts)
+        evict_least_recent_layout();
+
+    TextLayout *layout = create<TextLayout>();
+    for (int i = 0; i < letters.length(); i += 1)
+        ok_or_panic(layout->letters.append(letters.at(i)));
+    layout->width = width;
+    layout->last_use = ++_layout_use_count;
+    _layout_cache.put(text, layout);
+}
+
+bool FontSize::get_metrics(const ByteBuffer &text, TextMetrics *out_metrics) {
+    auto *entry = _metrics_cache.maybe_get(text);
+    if (!entry)
+        return false;
+    entry->value.last_use = ++_metrics_use_count;
+    *out_metrics = entry->value;
+    return true;
+}
+
+void FontSize::put_metrics(const ByteBuffer &text, int width, int advance) {
+    if (_metrics_cache.maybe_get(text))
+        return;
+    if (_metrics_cache.size() >= max_cached_metrics)
+        evict_least_recent_metrics();
+
+    TextMetrics metrics;
+    metrics.width = width;
+    metrics.advance = advance;
+    metrics.last_use = ++_metrics_use_count;
+    _metrics_cache.put(text, metrics);
+}
+
+void FontSize::evict_least_recent_metrics() {
+    HashMap<ByteBuffer, TextMetrics, ByteBuffer::hash>::Entry *oldest = nullptr;
+    auto it = _metrics_cache.entry_iterator();
+    for (;;) {
+        auto *entry = it.next();
+        if (!entry)
+            break;
+        if (!oldest || entry->value.last_use < oldest->value.last_use)
+            oldest = entry;
+    }
+    if (!oldest)
+        return;
+    ByteBuffer key = oldest->key;
+    _metrics_cache.remove(key);
+}
+
+// swaps the rendered .notdef glyph for a hollow box sitting on the baseline,
+// or for nothing, according to _notdef_style. the new bitmap is allocated by
+// freetype so that FT_Done_Glyph frees it like any other.
+void FontSize::replace_notdef_bitmap(FT_Glyph glyph, FT_BitmapGlyph bitmap_glyph) {
+    FT_Library library = glyph->library;
+    ft_ok(FT_Bitmap_Done(library, &bitmap_glyph->bitmap));
+    NotdefBox notdef = notdef_box(_notdef_style, _font_size);
+    if (notdef.width > 0) {
+        ByteBuffer pixels;
+        notdef_box_pixels(notdef, pixels);
+        FT_Bitmap box;
+        FT_Bitmap_New(&box);
+        box.rows = notdef.height;
+        box.width = notdef.width;
+        box.pitch = notdef.width;
+        box.buffer = (unsigned char *)pixels.raw();
+        box.num_grays = 256;
+        box.pixel_mode = FT_PIXEL_MODE_GRAY;
+        ft_ok(FT_Bitmap_Copy(library, &box, &bitmap_glyph->bitmap));
+    }
+
+    bitmap_glyph->left = notdef.left;
+    bitmap_glyph->top = notdef.top;
+    glyph->advance.x = notdef.advance << 16;
+    glyph->advance.y = 0;
 }
 
 uint32_t hash_uint32_t(const uint32_t &x) {
@@ -43,10 +199,25 @@ FontCacheValue FontSize::font_cache_entry(uint32_t codepoint) {
     FT_UInt glyph_index = FT_Get_Char_Index(_font_face, codepoint);
     ft_ok(FT_Load_Glyph(_font_face, glyph_index, FT_LOAD_RENDER));
     FT_GlyphSlot glyph_slot = _font_face->glyph;
+    int vert_advance = glyph_slot->metrics.vertAdvance / 64;
+    int vert_bearing_x = glyph_slot->metrics.vertBearingX / 64;
+    int vert_bearing_y = glyph_slot->metrics.vertBearingY / 64;
     FT_Glyph glyph;
     ft_ok(FT_Get_Glyph(glyph_slot, &glyph));
     ft_ok(FT_Glyph_To_Bitmap(&glyph, FT_RENDER_MODE_NORMAL, NULL, 0));
     FT_BitmapGlyph bitmap_glyph = (FT_BitmapGlyph) glyph;
+    if (glyph_index == 0 && _notdef_style != NotdefStyleFontDefault) {
+        replace_notdef_bitmap(glyph, bitmap_glyph);
+        if (_notdef_style == NotdefStyleHidden) {
+            vert_advance = 0;
+            vert_bearing_x = 0;
+            vert_bearing_y = 0;
+        } else {
+            vert_advance = _font_size;
+            vert_bearing_x = -(int)bitmap_glyph->bitmap.width / 2;
+            vert_bearing_y = (_font_size - (int)bitmap_glyph->bitmap.rows) / 2;
+        }
+    }
 
     int bmp_start_top = bitmap_glyph->top;
     int bmp_height = bitmap_glyph->bitmap.rows;
@@ -58,7 +229,8 @@ FontCacheValue FontSize::font_cache_entry(uint32_t codepoint) {
     if (this_below_size > _max_below_size)
         _max_below_size = this_below_size;
 
-    FontCacheValue value = FontCacheValue{glyph, bitmap_glyph, glyph_index, this_above_size, this_below_size};
+    FontCacheValue value = FontCacheValue{glyph, bitmap_glyph, glyph_index, this_above_size, this_below_size,
+        vert_advance, vert_bearing_x, vert_bearing_y};
     _font_cache.put(codepoint, value);
     return value;
 }
@@ -3,6 +3,8 @@
 
 #include "hash_map.hpp"
 #include "freetype.hpp"
+#include "byte_buffer.hpp"
+#include "notdef_box.hpp"
 
 #include <stdint.h>
 
@@ -14,6 +16,45 @@ struct FontCacheValue {
     FT_UInt glyph_index;
     int above_size;
     int below_size;
+    // for top to bottom layout, in pixels. bearing x is relative to the
+    // center line of the column and bearing y to the top of the cell
+    int vert_advance;
+    int vert_bearing_x;
+    int vert_bearing_y;
+};
+
+// where one glyph of a string goes, as computed by Label::update()
+struct TextLayoutLetter {
+    uint32_t codepoint;
+
+    int left; // half-way between prev letter and this one. 0 for first letter
+    int bitmap_left; // left + bitmap_left is the first pixel of the letter
+    int bitmap_width; // left + bitmap_left + bitmap_width is the last pixel of the letter
+    int full_width; // left + full_width is half-way between this letter and next
+
+    int above_size;
+    int below_size;
+    int bitmap_top;
+
+    // the top of the letter's cell in vertical layout, or of its line
+    int top;
+};
+
+// the size of a string, as computed by Label::measure()
+struct TextMetrics {
+    // up to the last inked pixel
+    int width;
+    // pen position after the last letter, including trailing whitespace
+    int advance;
+    // for evicting the least recently used metrics
+    uint64_t last_use;
+};
+
+struct TextLayout {
+    List<TextLayoutLetter> letters;
+    int width;
+    // for evicting the least recently used layout
+    uint64_t last_use;
 };
 
 class FontSize {
@@ -23,14 +64,63 @@ public:
 
     FontCacheValue font_cache_entry(uint32_t codepoint);
 
+    // drops every cached glyph, layout and measurement and starts over with
+    // font_face
+    void set_font_face(FT_Face font_face);
+    FT_Face font_face() const {
+        return _font_face;
+    }
+    // drops every cached glyph, layout and measurement
+    void clear_cache();
+    // drops every cached glyph if the style changes. default
+    // NotdefStyleFontDefault
+    void set_notdef_style(NotdefStyle notdef_style);
+
+    int size() const {
+        return _font_size;
+    }
+
+    int glyph_count() const {
+        return _font_cache.size();
+    }
+
+    // layouts of recently rendered strings, keyed by UTF-8 text. returns
+    // nullptr if text is not cached. the result is invalidated by put_layout
+    const TextLayout *get_layout(const ByteBuffer &text);
+    void put_layout(const ByteBuffer &text, const List<TextLayoutLetter> &letters, int width);
+
+    static const int max_cached_layouts = 256;
+    int layout_cache_hits;
+    int layout_cache_misses;
+
+    // kerned measurements of recently measured strings, keyed by UTF-8
+    // text. layout measures the same strings many times per frame, often
+    // without rendering them. returns false if text is not cached
+    bool get_metrics(const ByteBuffer &text, TextMetrics *out_metrics);
+    void put_metrics(const ByteBuffer &text, int width, int advance);
+
+    static const int max_cached_metrics = 1024;
+
     int _max_above_size;
     int _max_below_size;
 
 private:
     HashMap<uint32_t, FontCacheValue, hash_uint32_t> _font_cache;
+    HashMap<ByteBuffer, TextLayout *, ByteBuffer::hash> _layout_cache;
+    uint64_t _layout_use_count;
+    HashMap<ByteBuffer, TextMetrics, ByteBuffer::hash> _metrics_cache;
+    uint64_t _metrics_use_count;
 
     FT_Face _font_face;
     int _font_size;
+    NotdefStyle _notdef_style;
+
+    void prefill_cache();
+    void replace_notdef_bitmap(FT_Glyph glyph, FT_BitmapGlyph bitmap_glyph);
+    void clear_glyph_cache();
+    void clear_layout_cache();
+    void evict_least_recent_layout();
+    void evict_least_recent_metrics();
 
     FontSize &operator=(const FontSize&) = delete;
     FontSize(const FontSize&) = delete;
@@ -17,6 +17,61 @@ struct UnicodeCharacter {
     uint32_t upper;
 };
 
+struct UnicodeRange {
+    uint32_t first;
+    uint32_t last;
+};
+
+struct CombiningClassRange {
+    uint32_t first;
+    uint32_t last;
+    uint8_t combining_class;
+};
+
+// second is 0 for a decomposition to a single codepoint
+struct CanonicalDecomposition {
+    uint32_t codepoint;
+    uint32_t first;
+    uint32_t second;
+};
+
+struct CanonicalComposition {
+    uint32_t first;
+    uint32_t second;
+    uint32_t composed;
+};
+
+// characters that have a canonical decomposition to two codepoints but
+// that NFC never composes, from CompositionExclusions.txt. singletons and
+// non-starter decompositions are excluded by the rules below instead.
+static const uint32_t composition_exclusions[] = {
+    0x958, 0x959, 0x95a, 0x95b, 0x95c, 0x95d, 0x95e, 0x95f,
+    0x9dc, 0x9dd, 0x9df, 0xa33, 0xa36, 0xa59, 0xa5a, 0xa5b, 0xa5e,
+    0xb5c, 0xb5d, 0xf43, 0xf4d, 0xf52, 0xf57, 0xf5c, 0xf69, 0xf76, 0xf78,
+    0xf93, 0xf9d, 0xfa2, 0xfa7, 0xfac, 0xfb9,
+    0xfb1d, 0xfb1f, 0xfb2a, 0xfb2b, 0xfb2c, 0xfb2d, 0xfb2e, 0xfb2f, 0xfb30,
+    0xfb31, 0xfb32, 0xfb33, 0xfb34, 0xfb35, 0xfb36, 0xfb38, 0xfb39, 0xfb3a,
+    0xfb3b, 0xfb3c, 0xfb3e, 0xfb40, 0xfb41, 0xfb43, 0xfb44, 0xfb46, 0xfb47,
+    0xfb48, 0xfb49, 0xfb4a, 0xfb4b, 0xfb4c, 0xfb4d, 0xfb4e,
+    0x2adc, 0x1d15e, 0x1d15f, 0x1d160, 0x1d161, 0x1d162, 0x1d163, 0x1d164,
+    0x1d1bb, 0x1d1bc, 0x1d1bd, 0x1d1be, 0x1d1bf, 0x1d1c0};
+
+static bool is_composition_exclusion(uint32_t codepoint) {
+    for (long i = 0; i < array_length(composition_exclusions); i += 1) {
+        if (composition_exclusions[i] == codepoint)
+            return true;
+    }
+    return false;
+}
+
+static int compare_compositions(CanonicalComposition a, CanonicalComposition b) {
+    if (a.first != b.first)
+        return (a.first < b.first) ? -1 : 1;
+    if (a.second != b.second)
+        return (a.second < b.second) ? -1 : 1;
+    return 0;
+}
+
 int main(int argc, char *argv[]) {
     char *unicode_data_text_path = argv[1];
     char *out_header_path = argv[2];
@@ -55,6 +110,11 @@ int main(int argc, char *argv[]) {
 
     uint32_t max = 0;
     List<UnicodeCharacter> case_info_list;
+    // nonspacing and enclosing marks, which draw over the character before
+    // them rather than after it
+    List<UnicodeRange> combining_marks;
+    List<CombiningClassRange> combining_classes;
+    List<CanonicalDecomposition> decompositions;
 
     for (uint32_t i = 0; i < (uint32_t)lines.length(); i += 1) {
         List<ByteBuffer> line_fields;
@@ -65,6 +125,38 @@ int main(int argc, char *argv[]) {
         uint32_t codepoint;
         sscanf(line_fields.at(0).raw(), "%X", &codepoint);
 
+        ByteBuffer category = line_fields.at(2);
+        if (category == "Mn" || category == "Me") {
+            if (combining_marks.length() > 0 && combining_marks.last().last + 1 == codepoint)
+                combining_marks.last().last = codepoint;
+            else
+                ok_or_panic(combining_marks.append({ codepoint, codepoint }));
+        }
+
+        int combining_class;
+        sscanf(line_fields.at(3).raw(), "%d", &combining_class);
+        if (combining_class != 0) {
+            if (combining_classes.length() > 0 && combining_classes.last().last + 1 == codepoint &&
+                combining_classes.last().combining_class == combining_class)
+            {
+                combining_classes.last().last = codepoint;
+            } else {
+                ok_or_panic(combining_classes.append({ codepoint, codepoint, (uint8_t)combining_class }));
+            }
+        }
+
+        // compatibility decompositions start with a <tag> and are not
+        // used by NFC
+        ByteBuffer decomposition_str = line_fields.at(5);
+        if (decomposition_str.length() > 0 && decomposition_str.at(0) != '<') {
+            CanonicalDecomposition decomposition = { codepoint, 0, 0 };
+            int count = sscanf(decomposition_str.raw(), "%X %X",
+                    &decomposition.first, &decomposition.second);
+            if (count < 1)
+                panic("invalid decomposition for 0x%x", codepoint);
+            ok_or_panic(decompositions.append(decomposition));
+        }
+
         while (codepoint > (uint32_t)case_info_list.length()) {
             ok_or_panic(case_info_list.append({ 0, 0 }));
         }
@@ -81,6 +173,28 @@ int main(int argc, char *argv[]) {
         ok_or_panic(case_info_list.append({ lower, upper }));
     }
 
+    List<CanonicalComposition> compositions;
+    for (int i = 0; i < decompositions.length(); i += 1) {
+        CanonicalDecomposition *decomposition = &decompositions.at(i);
+        if (decomposition->second == 0 || is_composition_exclusion(decomposition->codepoint))
+            continue;
+        bool non_starter = false;
+        for (int j = 0; j < combining_classes.length(); j += 1) {
+            CombiningClassRange *range = &combining_classes.at(j);
+            if ((decomposition->codepoint >= range->first && decomposition->codepoint <= range->last) ||
+                (decomposition->first >= range->first && decomposition->first <= range->last))
+            {
+                non_starter = true;
+                break;
+            }
+        }
+        if (non_starter)
+            continue;
+        ok_or_panic(compositions.append({ decomposition->first, decomposition->second,
+                    decomposition->codepoint }));
+    }
+    compositions.sort<compare_compositions>();
+
     fprintf(out, "// This file is auto-generated.\n");
     fprintf(out, "#ifndef UNICODE_HPP\n");
     fprintf(out, "#define UNICODE_HPP\n");
@@ -104,6 +218,56 @@ int main(int argc, char *argv[]) {
     }
 
     fprintf(out, "};\n");
+
+    fprintf(out, "struct UnicodeRange {\n");
+    fprintf(out, "    uint32_t first;\n");
+    fprintf(out, "    uint32_t last;\n");
+    fprintf(out, "};\n");
+    fprintf(out, "static const UnicodeRange combining_marks[] = {\n");
+    for (int i = 0; i < combining_marks.length(); i += 1) {
+        UnicodeRange *range = &combining_marks.at(i);
+        fprintf(out, "  {0x%x, 0x%x},\n", range->first, range->last);
+    }
+    fprintf(out, "};\n");
+
+    fprintf(out, "struct CombiningClassRange {\n");
+    fprintf(out, "    uint32_t first;\n");
+    fprintf(out, "    uint32_t last;\n");
+    fprintf(out, "    uint8_t combining_class;\n");
+    fprintf(out, "};\n");
+    fprintf(out, "static const CombiningClassRange combining_classes[] = {\n");
+    for (int i = 0; i < combining_classes.length(); i += 1) {
+        CombiningClassRange *range = &combining_classes.at(i);
+        fprintf(out, "  {0x%x, 0x%x, %d},\n", range->first, range->last, range->combining_class);
+    }
+    fprintf(out, "};\n");
+
+    fprintf(out, "struct CanonicalDecomposition {\n");
+    fprintf(out, "    uint32_t codepoint;\n");
+    fprintf(out, "    uint32_t first;\n");
+    fprintf(out, "    uint32_t second;\n");
+    fprintf(out, "};\n");
+    fprintf(out, "static const CanonicalDecomposition canonical_decompositions[] = {\n");
+    for (int i = 0; i < decompositions.length(); i += 1) {
+        CanonicalDecomposition *decomposition = &decompositions.at(i);
+        fprintf(out, "  {0x%x, 0x%x, 0x%x},\n", decomposition->codepoint,
+                decomposition->first, decomposition->second);
+    }
+    fprintf(out, "};\n");
+
+    fprintf(out, "struct CanonicalComposition {\n");
+    fprintf(out, "    uint32_t first;\n");
+    fprintf(out, "    uint32_t second;\n");
+    fprintf(out, "    uint32_t composed;\n");
+    fprintf(out, "};\n");
+    fprintf(out, "static const CanonicalComposition canonical_compositions[] = {\n");
+    for (int i = 0; i < compositions.length(); i += 1) {
+        CanonicalComposition *composition = &compositions.at(i);
+        fprintf(out, "  {0x%x, 0x%x, 0x%x},\n", composition->first,
+                composition->second, composition->composed);
+    }
+    fprintf(out, "};\n");
+
     fprintf(out, "#endif\n");
 
     if (fclose(out))
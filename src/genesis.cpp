@@ -631,6 +631,12 @@ void genesis_set_event_callback(struct GenesisContext *context,
     context->event_callback = callback;
 }
 
+void genesis_set_audio_file_decoder(struct GenesisContext *context,
+        struct GenesisAudioFileDecoder *decoder)
+{
+    context->audio_file_decoder = decoder;
+}
+
 struct GenesisNodeDescriptor *genesis_node_descriptor_find(
         GenesisPipeline *pipeline, const char *name)
 {
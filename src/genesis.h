@@ -86,6 +86,27 @@ struct GenesisAudioFileIterator {
     float *ptr;
 };
 
+// where genesis_audio_file_load gets samples from. ffmpeg is used unless
+// genesis_set_audio_file_decoder is given another, for formats ffmpeg does
+// not read or for tests that need known samples.
+struct GenesisAudioFileDecoder {
+    void *userdata;
+    // reads the header of filename and reports its format with
+    // genesis_audio_file_set_channel_layout, genesis_audio_file_set_sample_rate
+    // and genesis_audio_file_set_tag. returns a GenesisError
+    int (*open)(struct GenesisAudioFileDecoder *decoder, const char *filename,
+            struct GenesisAudioFile *audio_file);
+    // decodes up to max_frame_count frames into channel_buffers, one buffer
+    // per channel. sets *out_frame_count to 0 at the end of the file.
+    // returns a GenesisError
+    int (*read_buffer)(struct GenesisAudioFileDecoder *decoder, float **channel_buffers,
+            int max_frame_count, int *out_frame_count);
+    // seconds, as the file says. -1 if it does not
+    double (*duration)(struct GenesisAudioFileDecoder *decoder);
+    // called once after every open, including one that failed
+    void (*close)(struct GenesisAudioFileDecoder *decoder);
+};
+
 struct GenesisSoundBackend {
     struct GenesisContext *context;
     enum SoundIoBackend backend;
@@ -468,6 +489,16 @@ GENESIS_EXPORT int genesis_audio_file_sample_rate_index(int index);
 
 GENESIS_EXPORT int genesis_audio_file_load(struct GenesisContext *context,
         const char *input_filename, struct GenesisAudioFile **audio_file);
+// genesis_audio_file_load decodes with decoder, which must outlive its use,
+// from now on. NULL goes back to ffmpeg.
+GENESIS_EXPORT void genesis_set_audio_file_decoder(struct GenesisContext *context,
+        struct GenesisAudioFileDecoder *decoder);
+// copies the file at an http:// or https:// url to output_filename, so that
+// it can be loaded or imported like a local file. on_progress, which is
+// optional, is called after each chunk with the bytes received so far and
+// the total, which is -1 if the server does not say.
+GENESIS_EXPORT int genesis_audio_file_download(const char *url, const char *output_filename,
+        void (*on_progress)(void *userdata, long received, long total), void *userdata);
 
 GENESIS_EXPORT struct GenesisAudioFile *genesis_audio_file_create(
         struct GenesisContext *context, int sample_rate);
@@ -475,6 +506,8 @@ GENESIS_EXPORT void genesis_audio_file_set_sample_rate(struct GenesisAudioFile *
         int sample_rate);
 GENESIS_EXPORT int genesis_audio_file_set_channel_layout(struct GenesisAudioFile *audio_file,
         const struct SoundIoChannelLayout *channel_layout);
+GENESIS_EXPORT void genesis_audio_file_set_tag(struct GenesisAudioFile *audio_file,
+        const char *tag_key, int tag_key_len, const char *tag_value, int tag_value_len);
 
 GENESIS_EXPORT void genesis_audio_file_destroy(struct GenesisAudioFile *audio_file);
 
@@ -486,6 +519,28 @@ GENESIS_EXPORT const struct SoundIoChannelLayout *genesis_audio_file_channel_lay
         const struct GenesisAudioFile *audio_file);
 GENESIS_EXPORT long genesis_audio_file_frame_count(const struct GenesisAudioFile *audio_file);
 GENESIS_EXPORT int genesis_audio_file_sample_rate(const struct GenesisAudioFile *audio_file);
+// the value of a tag such as "title", "artist" or "album", compared without
+// regard to case. returns NULL if the file does not have the tag.
+GENESIS_EXPORT const char *genesis_audio_file_tag(const struct GenesisAudioFile *audio_file,
+        const char *key);
+// seconds of audio decoded, from the frame count and sample rate
+GENESIS_EXPORT double genesis_audio_file_duration(const struct GenesisAudioFile *audio_file);
+// seconds, as the file's container reports it. this can differ slightly
+// from genesis_audio_file_duration. returns -1 if the container gives no
+// duration, and for audio files that were created rather than loaded.
+GENESIS_EXPORT double genesis_audio_file_container_duration(const struct GenesisAudioFile *audio_file);
+// the frame nearest to frame, at most max_distance away, where the sum of all
+// channels crosses zero. editing there avoids clicks. returns frame if there
+// is no zero crossing that close.
+GENESIS_EXPORT long genesis_audio_file_find_zero_crossing(const struct GenesisAudioFile *audio_file,
+        long frame, long max_distance);
+// how alike the left and right channels are from start_frame up to
+// end_frame, from 1 when they are the same, through 0 when they are
+// unrelated or silent, to -1 when one is the other inverted, which cancels
+// out when summed to mono. slide the range along for a phase meter.
+// returns GenesisErrorIncompatibleChannelLayouts unless there are 2 channels.
+GENESIS_EXPORT int genesis_audio_file_stereo_correlation(const struct GenesisAudioFile *audio_file,
+        long start_frame, long end_frame, double *out_correlation);
 
 GENESIS_EXPORT struct GenesisAudioFileIterator genesis_audio_file_iterator(
         struct GenesisAudioFile *audio_file, int channel_index, long start_frame_index);
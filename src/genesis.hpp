@@ -31,6 +31,8 @@ struct GenesisContext {
 
     List<GenesisRenderFormat*> out_formats;
     List<GenesisAudioFileFormat*> in_formats;
+    // null for ffmpeg
+    GenesisAudioFileDecoder *audio_file_decoder;
 
     List<GenesisPipeline*> pipelines;
 };
@@ -17,7 +17,12 @@
 #include "audio_graph.hpp"
 #include "project_props_widget.hpp"
 #include "render_widget.hpp"
+#include "level_meter_widget.hpp"
+#include "correlation_meter_widget.hpp"
 #include "render_job.hpp"
+#include "overlay.hpp"
+#include "color.hpp"
+#include "config.h"
 
 static void exit_handler(void *userdata) {
     GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
@@ -81,15 +86,75 @@ static void always_show_tabs_handler(void *userdata) {
     genesis_editor->save_window_config();
 }
 
+static int fps_widget_width(bool show_perf_stats) {
+    return show_perf_stats ? 380 : 50;
+}
+
+static void reload_font_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->reload_font();
+}
+
+static void perf_stats_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->toggle_perf_stats();
+}
+
+// outlines the widget under the mouse in the window's overlay and labels it
+// with its size, for checking layout along with the perf stats
+static void draw_layout_guide(EditorWindow *editor_window, bool show) {
+    GuiWindow *window = editor_window->window;
+    Overlay *overlay = window->overlay;
+    overlay->begin_frame();
+    Widget *widget = window->_mouse_over_widget;
+    if (show && widget) {
+        glm::vec4 color = color_layout_guide();
+        int right = widget->left + widget->width - 1;
+        int bottom = widget->top + widget->height - 1;
+        overlay->draw_line(widget->left, widget->top, right, widget->top, color, 1);
+        overlay->draw_line(widget->left, bottom, right, bottom, color, 1);
+        overlay->draw_line(widget->left, widget->top, widget->left, bottom, color, 1);
+        overlay->draw_line(right, widget->top, right, bottom, color, 1);
+        ByteBuffer size_text;
+        size_text.format("%dx%d", widget->width, widget->height);
+        overlay->draw_text(widget->left + 2, widget->top + 2, size_text, color);
+    }
+    overlay->end_frame();
+    editor_window->layout_guide_shown = show;
+}
+
 static void on_flush_events(Event, void *userdata) {
     GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
 
     // update FPS labels
+    Gui *gui = genesis_editor->gui;
+    // the stats change every frame, and so does render progress
+    if (genesis_editor->show_perf_stats || gui->render_jobs.length() > 0)
+        gui->keep_animating();
     ByteBuffer fps_text;
-    fps_text.format("%.0f fps", genesis_editor->gui->fps);
+    if (genesis_editor->show_perf_stats) {
+        int layout_hits, layout_misses;
+        gui->font_library->layout_cache_stats(&layout_hits, &layout_misses);
+        fps_text.format("%.0f fps  %.1f ms  %d glyphs  %d/%d layouts", gui->fps,
+                gui->frame_time * 1000.0, gui->font_library->glyph_cache_count(), layout_hits,
+                layout_hits + layout_misses);
+    } else {
+        fps_text.format("%.0f fps", gui->fps);
+    }
     for (int i = 0; i < genesis_editor->windows.length(); i += 1) {
         EditorWindow *editor_window = genesis_editor->windows.at(i);
-        editor_window->fps_widget->set_text(fps_text);
+        if (genesis_editor->show_perf_stats) {
+            ByteBuffer window_text;
+            GuiWindow *window = editor_window->window;
+            window_text.format("%s  %d/%d widgets", fps_text.raw(), window->drawn_widget_count,
+                    window->drawn_widget_count + window->culled_widget_count);
+            editor_window->fps_widget->set_text(window_text);
+            draw_layout_guide(editor_window, true);
+        } else {
+            editor_window->fps_widget->set_text(fps_text);
+            if (editor_window->layout_guide_shown)
+                draw_layout_guide(editor_window, false);
+        }
     }
 
     audio_graph_flush_events(genesis_editor->audio_graph);
@@ -139,10 +204,81 @@ static void stop_playback_handler(void *userdata) {
     genesis_editor->stop_playback();
 }
 
-GenesisEditor::GenesisEditor() :
+static void snap_to_zero_crossing_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->toggle_snap_to_zero_crossing();
+}
+
+static void reset_key_bindings_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->reset_key_bindings();
+}
+
+// tolerance when jumping between markers, so that pressing the key again moves
+// past the marker the play head was just moved to
+static const double MARKER_EPSILON = 0.000001;
+
+static void add_marker_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->add_marker_at_play_head();
+}
+
+static void previous_marker_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->jump_to_previous_marker();
+}
+
+static void next_marker_handler(void *userdata) {
+    GenesisEditor *genesis_editor = (GenesisEditor *)userdata;
+    genesis_editor->jump_to_next_marker();
+}
+
+static bool file_exists(const ByteBuffer &path) {
+    FILE *f = fopen(path.raw(), "rb");
+    if (!f)
+        return false;
+    fclose(f);
+    return true;
+}
+
+// GENESIS_RESOURCES overrides the search. otherwise look next to the
+// executable, which is where the build puts it, then where `make install`
+// puts it, then the working directory.
+static void find_resource_bundle(ByteBuffer &out) {
+    static const char *bundle_name = "resources.bundle";
+
+    const char *env_path = getenv("GENESIS_RESOURCES");
+    if (env_path) {
+        out = env_path;
+        if (!file_exists(out))
+            panic("GENESIS_RESOURCES is set to %s, which does not exist", env_path);
+        return;
+    }
+
+    ByteBuffer exe_dir;
+    if (!os_get_exe_dir(exe_dir)) {
+        os_path_join(out, exe_dir, bundle_name);
+        if (file_exists(out))
+            return;
+    }
+
+    os_path_join(out, GENESIS_DATA_DIR, bundle_name);
+    if (file_exists(out))
+        return;
+
+    out = bundle_name;
+    if (file_exists(out))
+        return;
+
+    panic("unable to find %s next to the executable, in " GENESIS_DATA_DIR
+            " or in the working directory. set GENESIS_RESOURCES to its path.", bundle_name);
+}
+
+GenesisEditor::GenesisEditor(const GenesisEditorOptions *options) :
     project(nullptr)
 {
     underrun_count = 0;
+    show_perf_stats = false;
 
     int err;
 
@@ -160,12 +296,27 @@ GenesisEditor::GenesisEditor() :
     os_get_app_config_path(config_path);
     settings_file = settings_file_open(config_path);
 
-    resource_bundle = create<ResourceBundle>("resources.bundle");
+    ByteBuffer resource_bundle_path;
+    find_resource_bundle(resource_bundle_path);
+    resource_bundle = create<ResourceBundle>(resource_bundle_path.raw());
 
     if ((err = genesis_context_create(&genesis_context)))
         panic("unable to create genesis context: %s", genesis_strerror(err));
 
-    gui = create<Gui>(genesis_context, resource_bundle);
+    ByteBuffer font_buffer;
+    const char *env_font_path = getenv("GENESIS_FONT");
+    if (!env_font_path || os_read_file(env_font_path, font_buffer) || font_buffer.length() == 0) {
+        if (env_font_path)
+            fprintf(stderr, "unable to read GENESIS_FONT %s, using the bundled font\n", env_font_path);
+        resource_bundle->get_file_buffer("font.ttf", font_buffer);
+    }
+    font_library = create<FontLibrary>(font_buffer);
+    gui = create<Gui>(genesis_context, resource_bundle, font_library);
+    gui->set_high_contrast(options->high_contrast);
+    if (options->font_size > 0)
+        gui->default_font_size = options->font_size;
+    gui->set_integer_scale(options->scale);
+    gui->set_msaa_samples(options->msaa_samples);
 
     gui->events.attach_handler(EventFlushEvents, on_flush_events, this);
     gui->events.attach_handler(EventSoundBackendDisconnected, on_sound_backend_disconnected, this);
@@ -185,6 +336,9 @@ GenesisEditor::GenesisEditor() :
         settings_dirty = true;
     }
 
+    if (load_key_bindings())
+        settings_dirty = true;
+
     int out_format_count = genesis_out_format_count(genesis_context);
     for (int i = 0; i < out_format_count; i += 1) {
         GenesisRenderFormat *render_format = genesis_out_format_index(genesis_context, i);
@@ -240,12 +394,21 @@ GenesisEditor::GenesisEditor() :
         ok_or_panic(project_create(genesis_context, proj_path.raw(), id, user, &project));
 
         settings_file->open_project_id = id;
+        settings_file->open_project_play_head = 0.0;
+        settings_file->open_project_scroll_pos = 0.0;
+        settings_file_clear_markers(settings_file);
         settings_dirty = true;
     }
 
     ok_or_panic(audio_graph_create_playback(project, genesis_context,
                 settings_file, &audio_graph));
 
+    if (options->restore_session && !create_new)
+        audio_graph_set_play_head(audio_graph, settings_file->open_project_play_head);
+
+    for (int i = 0; i < options->import_paths.length(); i += 1)
+        import_audio_file(options->import_paths.at(i));
+
     if (settings_file->open_windows.length() == 0) {
         create_sf_open_window();
         settings_dirty = true;
@@ -274,6 +437,10 @@ GenesisEditor::GenesisEditor() :
         SettingsFileOpenWindow *sf_open_window = &settings_file->open_windows.at(i);
         create_window(sf_open_window);
     }
+    if (options->restore_session && !create_new) {
+        for (int i = 0; i < windows.length(); i += 1)
+            windows.at(i)->track_editor->set_scroll_pos(settings_file->open_project_scroll_pos);
+    }
 
     if (settings_dirty)
         settings_file_commit(settings_file);
@@ -285,10 +452,28 @@ GenesisEditor::GenesisEditor() :
 }
 
 GenesisEditor::~GenesisEditor() {
+    settings_file->open_project_play_head = audio_graph_play_head_pos(audio_graph);
+    if (windows.length() > 0)
+        settings_file->open_project_scroll_pos = windows.at(0)->track_editor->scroll_pos();
+    settings_file_commit(settings_file);
+
+    // join the render threads of any windows still open and stop render jobs
+    // before tearing down the project they reference
+    while (windows.length() > 0) {
+        EditorWindow *editor_window = windows.pop();
+        destroy_error(editor_window);
+        gui->destroy_window(editor_window->window);
+        destroy(editor_window, 1);
+    }
+    destroy(gui, 1);
+    destroy(font_library, 1);
+
     audio_graph_destroy(audio_graph);
     project_close(project);
     user_destroy(user);
     genesis_context_destroy(genesis_context);
+    destroy(resource_bundle, 1);
+    settings_file_close(settings_file);
 }
 
 void GenesisEditor::create_editor_window() {
@@ -308,7 +493,11 @@ int GenesisEditor::window_index(EditorWindow *window) {
 void GenesisEditor::close_window(EditorWindow *editor_window) {
     int index = window_index(editor_window);
     bool last_one = (windows.length() == 1);
+    // the destructor saves the scroll position of whichever window is left
+    if (last_one)
+        settings_file->open_project_scroll_pos = editor_window->track_editor->scroll_pos();
     windows.swap_remove(index);
+    destroy_error(editor_window);
     gui->destroy_window(editor_window->window);
     if (!last_one)
         save_window_config();
@@ -324,11 +513,75 @@ void GenesisEditor::close_others(EditorWindow *window) {
     save_window_config();
 }
 
+int GenesisEditor::import_audio_file(const ByteBuffer &path) {
+    AudioAsset *audio_asset;
+    int err;
+    if ((err = project_add_audio_asset(project, path, &audio_asset))) {
+        if (err == GenesisErrorAlreadyExists)
+            return 0;
+        fprintf(stderr, "Unable to import %s: %s\n", path.raw(), genesis_strerror(err));
+        return err;
+    }
+    return 0;
+}
+
+void GenesisEditor::import_dropped_files(EditorWindow *editor_window) {
+    List<ByteBuffer> *dropped_files = &editor_window->window->dropped_files;
+    ByteBuffer message;
+    int failed_count = 0;
+    for (int i = 0; i < dropped_files->length(); i += 1) {
+        const ByteBuffer &path = dropped_files->at(i);
+        int err = import_audio_file(path);
+        if (!err)
+            continue;
+        if (failed_count == 0)
+            message.format("Unable to import %s: %s", path.raw(), genesis_strerror(err));
+        failed_count += 1;
+    }
+    if (failed_count == 0)
+        return;
+    if (failed_count > 1) {
+        ByteBuffer more;
+        more.format(" (and %d more)", failed_count - 1);
+        message.append(more);
+    }
+    show_error(editor_window, message);
+}
+
+static void on_error_dismiss(void *userdata) {
+    EditorWindow *editor_window = (EditorWindow *)userdata;
+    editor_window->genesis_editor->destroy_error(editor_window);
+}
+
+void GenesisEditor::show_error(EditorWindow *editor_window, const ByteBuffer &message) {
+    // a later error replaces the one on screen
+    destroy_error(editor_window);
+    TextWidget *error_text = create<TextWidget>(editor_window->window);
+    error_text->set_text_interaction(false);
+    error_text->set_auto_size(true);
+    error_text->set_text(message);
+    editor_window->error_text = error_text;
+    editor_window->window->show_modal(error_text, on_error_dismiss, editor_window);
+}
+
+void GenesisEditor::destroy_error(EditorWindow *editor_window) {
+    if (!editor_window->error_text)
+        return;
+    destroy(editor_window->error_text, 1);
+    editor_window->error_text = nullptr;
+    editor_window->layout_guide_shown = false;
+}
+
 static void static_on_close_event(Event, void *userdata) {
     EditorWindow *editor_window = (EditorWindow *)userdata;
     editor_window->genesis_editor->close_window(editor_window);
 }
 
+static void static_on_files_dropped(Event, void *userdata) {
+    EditorWindow *editor_window = (EditorWindow *)userdata;
+    editor_window->genesis_editor->import_dropped_files(editor_window);
+}
+
 static void save_window_config_handler(Event, void *userdata) {
     EditorWindow *editor_window = (EditorWindow *)userdata;
     editor_window->genesis_editor->save_window_config();
@@ -355,6 +608,7 @@ void GenesisEditor::create_window(SettingsFileOpenWindow *sf_open_window) {
             sf_open_window->width, sf_open_window->height);
     new_window->_userdata = editor_window;
     new_window->events.attach_handler(EventWindowClose, static_on_close_event, editor_window);
+    new_window->events.attach_handler(EventWindowFilesDropped, static_on_files_dropped, editor_window);
     new_window->events.attach_handler(EventWindowPosChange, save_window_config_handler, editor_window);
     new_window->events.attach_handler(EventWindowSizeChange, save_window_config_handler, editor_window);
     new_window->events.attach_handler(EventPerspectiveChange, save_window_config_handler, editor_window);
@@ -377,66 +631,120 @@ void GenesisEditor::create_window(SettingsFileOpenWindow *sf_open_window) {
     MenuWidgetItem *window_menu = editor_window->menu_widget->add_menu("&Window");
     MenuWidgetItem *help_menu = editor_window->menu_widget->add_menu("&Help");
 
-    MenuWidgetItem *exit_menu = project_menu->add_menu("E&xit", ctrl_shortcut(VirtKeyQ));
-
-    MenuWidgetItem *undo_menu = edit_menu->add_menu("&Undo", ctrl_shortcut(VirtKeyZ));
-    MenuWidgetItem *redo_menu = edit_menu->add_menu("&Redo", ctrl_shift_shortcut(VirtKeyZ));
-
-    MenuWidgetItem *toggle_playback_menu = playback_menu->add_menu("&Play", shortcut(VirtKeySpace));
-    MenuWidgetItem *restart_playback_menu = playback_menu->add_menu("&Restart", shortcut(VirtKeyEnter));
-    MenuWidgetItem *stop_playback_menu = playback_menu->add_menu("&Stop", shift_shortcut(VirtKeyEnter));
-
-    MenuWidgetItem *new_window_menu = window_menu->add_menu("&New Window", no_shortcut());
-    MenuWidgetItem *close_window_menu = window_menu->add_menu("&Close", alt_shortcut(VirtKeyF4));
+    MenuWidgetItem *exit_menu = project_menu->add_menu("E&xit", key_bindings[KeyBindingActionExit]);
+
+    MenuWidgetItem *undo_menu = edit_menu->add_menu("&Undo", key_bindings[KeyBindingActionUndo]);
+    MenuWidgetItem *redo_menu = edit_menu->add_menu("&Redo", key_bindings[KeyBindingActionRedo]);
+    MenuWidgetItem *snap_to_zero_crossing_menu = edit_menu->add_menu("Snap to &Zero Crossing",
+            key_bindings[KeyBindingActionSnapToZeroCrossing]);
+
+    MenuWidgetItem *toggle_playback_menu = playback_menu->add_menu("&Play",
+            key_bindings[KeyBindingActionTogglePlayback]);
+    MenuWidgetItem *restart_playback_menu = playback_menu->add_menu("&Restart",
+            key_bindings[KeyBindingActionRestartPlayback]);
+    MenuWidgetItem *stop_playback_menu = playback_menu->add_menu("&Stop",
+            key_bindings[KeyBindingActionStopPlayback]);
+    MenuWidgetItem *add_marker_menu = playback_menu->add_menu("Add &Marker",
+            key_bindings[KeyBindingActionAddMarker]);
+    MenuWidgetItem *previous_marker_menu = playback_menu->add_menu("Pre&vious Marker",
+            key_bindings[KeyBindingActionPreviousMarker]);
+    MenuWidgetItem *next_marker_menu = playback_menu->add_menu("&Next Marker",
+            key_bindings[KeyBindingActionNextMarker]);
+
+    MenuWidgetItem *new_window_menu = window_menu->add_menu("&New Window",
+            key_bindings[KeyBindingActionNewWindow]);
+    MenuWidgetItem *close_window_menu = window_menu->add_menu("&Close",
+            key_bindings[KeyBindingActionCloseWindow]);
     MenuWidgetItem *close_others_menu = window_menu->add_menu("Close &Others", no_shortcut());
     MenuWidgetItem *always_show_tabs_menu = window_menu->add_menu("Always Show &Tabs", no_shortcut());
     MenuWidgetItem *show_view_menu = window_menu->add_menu("Show &View", no_shortcut());
-
-    MenuWidgetItem *open_wiki_menu = help_menu->add_menu("Genesis &Wiki", shortcut(VirtKeyF1));
+    MenuWidgetItem *perf_stats_menu = window_menu->add_menu("&Performance Stats",
+            key_bindings[KeyBindingActionTogglePerfStats]);
+    MenuWidgetItem *reload_font_menu = window_menu->add_menu("Reload &Font",
+            key_bindings[KeyBindingActionReloadFont]);
+    MenuWidgetItem *reset_key_bindings_menu = window_menu->add_menu("Reset &Key Bindings", no_shortcut());
+
+    MenuWidgetItem *open_wiki_menu = help_menu->add_menu("Genesis &Wiki",
+            key_bindings[KeyBindingActionOpenWiki]);
     MenuWidgetItem *report_bug_menu = help_menu->add_menu("&Report a Bug", no_shortcut());
 
     exit_menu->set_activate_handler(exit_handler, this);
 
     undo_menu->set_activate_handler(undo_handler, this);
     redo_menu->set_activate_handler(redo_handler, this);
+    snap_to_zero_crossing_menu->set_activate_handler(snap_to_zero_crossing_handler, this);
 
     toggle_playback_menu->set_activate_handler(toggle_playback_handler, this);
     restart_playback_menu->set_activate_handler(restart_playback_handler, this);
     stop_playback_menu->set_activate_handler(stop_playback_handler, this);
+    add_marker_menu->set_activate_handler(add_marker_handler, this);
+    previous_marker_menu->set_activate_handler(previous_marker_handler, this);
+    next_marker_menu->set_activate_handler(next_marker_handler, this);
 
     new_window_menu->set_activate_handler(new_window_handler, this);
     close_window_menu->set_activate_handler(close_window_handler, editor_window);
     close_others_menu->set_activate_handler(close_others_handler, editor_window);
     always_show_tabs_menu->set_activate_handler(always_show_tabs_handler, editor_window);
+    perf_stats_menu->set_activate_handler(perf_stats_handler, this);
+    reload_font_menu->set_activate_handler(reload_font_handler, this);
+    reset_key_bindings_menu->set_activate_handler(reset_key_bindings_handler, this);
 
     open_wiki_menu->set_activate_handler(open_wiki_handler, this);
     report_bug_menu->set_activate_handler(report_bug_handler, this);
 
     editor_window->undo_menu = undo_menu;
     editor_window->redo_menu = redo_menu;
+    editor_window->snap_to_zero_crossing_menu = snap_to_zero_crossing_menu;
     editor_window->always_show_tabs_menu = always_show_tabs_menu;
     editor_window->show_view_menu = show_view_menu;
+    editor_window->perf_stats_menu = perf_stats_menu;
     editor_window->toggle_playback_menu = toggle_playback_menu;
     editor_window->restart_playback_menu = restart_playback_menu;
     editor_window->stop_playback_menu = stop_playback_menu;
 
+    editor_window->action_menus[KeyBindingActionExit] = exit_menu;
+    editor_window->action_menus[KeyBindingActionUndo] = undo_menu;
+    editor_window->action_menus[KeyBindingActionRedo] = redo_menu;
+    editor_window->action_menus[KeyBindingActionSnapToZeroCrossing] = snap_to_zero_crossing_menu;
+    editor_window->action_menus[KeyBindingActionTogglePlayback] = toggle_playback_menu;
+    editor_window->action_menus[KeyBindingActionRestartPlayback] = restart_playback_menu;
+    editor_window->action_menus[KeyBindingActionStopPlayback] = stop_playback_menu;
+    editor_window->action_menus[KeyBindingActionAddMarker] = add_marker_menu;
+    editor_window->action_menus[KeyBindingActionPreviousMarker] = previous_marker_menu;
+    editor_window->action_menus[KeyBindingActionNextMarker] = next_marker_menu;
+    editor_window->action_menus[KeyBindingActionNewWindow] = new_window_menu;
+    editor_window->action_menus[KeyBindingActionCloseWindow] = close_window_menu;
+    editor_window->action_menus[KeyBindingActionTogglePerfStats] = perf_stats_menu;
+    editor_window->action_menus[KeyBindingActionReloadFont] = reload_font_menu;
+    editor_window->action_menus[KeyBindingActionOpenWiki] = open_wiki_menu;
+    editor_window->action_menus[KeyBindingActionSeekBackward] = nullptr;
+    editor_window->action_menus[KeyBindingActionSeekForward] = nullptr;
+
     TextWidget *fps_widget = create<TextWidget>(new_window);
     fps_widget->set_text_interaction(false);
     fps_widget->set_background_color(editor_window->menu_widget->bg_color);
-    fps_widget->set_min_width(50);
-    fps_widget->set_max_width(50);
+    fps_widget->set_min_width(fps_widget_width(show_perf_stats));
+    fps_widget->set_max_width(fps_widget_width(show_perf_stats));
     editor_window->fps_widget = fps_widget;
+    editor_window->error_text = nullptr;
 
     GridLayoutWidget *top_bar_grid_layout = create<GridLayoutWidget>(new_window);
     top_bar_grid_layout->padding = 0;
     top_bar_grid_layout->spacing = 0;
     top_bar_grid_layout->add_widget(editor_window->menu_widget, 0, 0, HAlignLeft, VAlignTop);
-    top_bar_grid_layout->add_widget(fps_widget, 0, 1, HAlignRight, VAlignTop);
+    LevelMeterWidget *level_meter = create<LevelMeterWidget>(new_window, audio_graph);
+    top_bar_grid_layout->add_widget(level_meter, 0, 1, HAlignRight, VAlignTop);
+    CorrelationMeterWidget *correlation_meter = create<CorrelationMeterWidget>(new_window, audio_graph);
+    top_bar_grid_layout->add_widget(correlation_meter, 0, 2, HAlignRight, VAlignTop);
+    top_bar_grid_layout->add_widget(fps_widget, 0, 3, HAlignRight, VAlignTop);
 
     ResourcesTreeWidget *resources_tree = create<ResourcesTreeWidget>(new_window, settings_file, audio_graph);
     add_dock(editor_window, resources_tree, "Resources");
 
     TrackEditorWidget *track_editor = create<TrackEditorWidget>(new_window, audio_graph);
+    track_editor->seek_backward_key = key_bindings[KeyBindingActionSeekBackward];
+    track_editor->seek_forward_key = key_bindings[KeyBindingActionSeekForward];
+    editor_window->track_editor = track_editor;
     add_dock(editor_window, track_editor, "Track Editor");
 
     MixerWidget *mixer = create<MixerWidget>(new_window, project);
@@ -511,8 +819,11 @@ void GenesisEditor::refresh_menu_state() {
         editor_window->redo_menu->set_enabled(redo_enabled);
         editor_window->redo_menu->set_caption(redo_caption);
 
+        editor_window->snap_to_zero_crossing_menu->set_icon(
+                settings_file->snap_to_zero_crossing ? gui->img_check : nullptr);
         editor_window->always_show_tabs_menu->set_icon(
                 editor_window->always_show_tabs ? gui->img_check : nullptr);
+        editor_window->perf_stats_menu->set_icon(show_perf_stats ? gui->img_check : nullptr);
 
         editor_window->toggle_playback_menu->set_caption(is_playing ? "&Pause" : "&Play");
 
@@ -520,6 +831,38 @@ void GenesisEditor::refresh_menu_state() {
     }
 }
 
+void GenesisEditor::toggle_perf_stats() {
+    show_perf_stats = !show_perf_stats;
+    int width = fps_widget_width(show_perf_stats);
+    for (int i = 0; i < windows.length(); i += 1) {
+        EditorWindow *editor_window = windows.at(i);
+        editor_window->fps_widget->set_min_width(width);
+        editor_window->fps_widget->set_max_width(width);
+    }
+    refresh_menu_state();
+}
+
+void GenesisEditor::reload_font() {
+    // the bundled font cannot change while genesis runs
+    const char *font_path = getenv("GENESIS_FONT");
+    if (!font_path) {
+        fprintf(stderr, "set GENESIS_FONT to the font file to reload\n");
+        return;
+    }
+    int err;
+    if ((err = font_library->reload_font(font_path))) {
+        fprintf(stderr, "unable to reload %s: %s\n", font_path, genesis_strerror(err));
+        return;
+    }
+    gui->request_redraw();
+}
+
+void GenesisEditor::toggle_snap_to_zero_crossing() {
+    settings_file->snap_to_zero_crossing = !settings_file->snap_to_zero_crossing;
+    settings_file_commit(settings_file);
+    refresh_menu_state();
+}
+
 void GenesisEditor::do_undo() {
     project_undo(project);
 }
@@ -673,3 +1016,108 @@ void GenesisEditor::restart_playback() {
 void GenesisEditor::stop_playback() {
     audio_graph_stop_playback(audio_graph);
 }
+
+static void append_default_key_binding(SettingsFile *settings_file, KeyBindingAction action) {
+    ok_or_panic(settings_file->key_bindings.add_one());
+    SettingsFileKeyBinding *sf_key_binding = &settings_file->key_bindings.last();
+    sf_key_binding->action = key_binding_action_name(action);
+    KeySequence seq = key_binding_default(action);
+    if (null_key_sequence(seq))
+        sf_key_binding->key_sequence = "";
+    else
+        sf_key_binding->key_sequence = key_sequence_to_string(seq).encode();
+}
+
+bool GenesisEditor::load_key_bindings() {
+    bool in_settings[KeyBindingActionCount];
+    for (int i = 0; i < KeyBindingActionCount; i += 1) {
+        key_bindings[i] = key_binding_default((KeyBindingAction)i);
+        in_settings[i] = false;
+    }
+
+    for (int i = 0; i < settings_file->key_bindings.length(); i += 1) {
+        SettingsFileKeyBinding *sf_key_binding = &settings_file->key_bindings.at(i);
+        KeyBindingAction action = key_binding_action_from_name(sf_key_binding->action);
+        if (action == KeyBindingActionCount) {
+            fprintf(stderr, "Ignoring key binding for unknown command: %s\n",
+                    sf_key_binding->action.raw());
+            continue;
+        }
+        in_settings[action] = true;
+        KeySequence seq;
+        if (!key_sequence_from_string(sf_key_binding->key_sequence, &seq)) {
+            fprintf(stderr, "Ignoring invalid key sequence for %s: %s\n",
+                    sf_key_binding->action.raw(), sf_key_binding->key_sequence.raw());
+            continue;
+        }
+        key_bindings[action] = seq;
+    }
+
+    // add the defaults for commands missing from the settings file, so that
+    // users can see what there is to change
+    bool added_any = false;
+    for (int i = 0; i < KeyBindingActionCount; i += 1) {
+        if (!in_settings[i]) {
+            append_default_key_binding(settings_file, (KeyBindingAction)i);
+            added_any = true;
+        }
+    }
+    return added_any;
+}
+
+void GenesisEditor::reset_key_bindings() {
+    settings_file->key_bindings.clear();
+    for (int i = 0; i < KeyBindingActionCount; i += 1) {
+        KeyBindingAction action = (KeyBindingAction)i;
+        append_default_key_binding(settings_file, action);
+        key_bindings[i] = key_binding_default(action);
+    }
+    settings_file_commit(settings_file);
+
+    for (int window_i = 0; window_i < windows.length(); window_i += 1) {
+        EditorWindow *editor_window = windows.at(window_i);
+        for (int i = 0; i < KeyBindingActionCount; i += 1) {
+            if (editor_window->action_menus[i])
+                editor_window->action_menus[i]->set_shortcut(key_bindings[i]);
+        }
+        editor_window->track_editor->seek_backward_key = key_bindings[KeyBindingActionSeekBackward];
+        editor_window->track_editor->seek_forward_key = key_bindings[KeyBindingActionSeekForward];
+    }
+}
+
+void GenesisEditor::add_marker(double whole_note_pos, const String &name) {
+    settings_file_add_marker(settings_file, whole_note_pos, name);
+    settings_file_commit(settings_file);
+}
+
+const List<SettingsFileMarker> &GenesisEditor::markers() const {
+    return settings_file->open_project_markers;
+}
+
+void GenesisEditor::add_marker_at_play_head() {
+    ByteBuffer name;
+    name.format("Marker %d", settings_file->open_project_markers.length() + 1);
+    add_marker(audio_graph_play_head_pos(audio_graph), String(name));
+}
+
+void GenesisEditor::jump_to_previous_marker() {
+    double play_head_pos = audio_graph_play_head_pos(audio_graph);
+    const List<SettingsFileMarker> &list = markers();
+    for (int i = list.length() - 1; i >= 0; i -= 1) {
+        if (list.at(i).pos < play_head_pos - MARKER_EPSILON) {
+            audio_graph_set_play_head(audio_graph, list.at(i).pos);
+            return;
+        }
+    }
+}
+
+void GenesisEditor::jump_to_next_marker() {
+    double play_head_pos = audio_graph_play_head_pos(audio_graph);
+    const List<SettingsFileMarker> &list = markers();
+    for (int i = 0; i < list.length(); i += 1) {
+        if (list.at(i).pos > play_head_pos + MARKER_EPSILON) {
+            audio_graph_set_play_head(audio_graph, list.at(i).pos);
+            return;
+        }
+    }
+}
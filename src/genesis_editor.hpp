@@ -3,6 +3,7 @@
 
 #include "key_event.hpp"
 #include "gui.hpp"
+#include "key_bindings.hpp"
 
 class GuiWindow;
 struct GenesisContext;
@@ -17,7 +18,9 @@ class TextWidget;
 struct SettingsFilePerspective;
 struct SettingsFileOpenWindow;
 struct SettingsFileDock;
+struct SettingsFileMarker;
 class DockablePaneWidget;
+class TrackEditorWidget;
 struct EditorWindow;
 struct AudioGraph;
 
@@ -32,21 +35,47 @@ struct EditorWindow {
     GuiWindow *window;
     MenuWidgetItem *undo_menu;
     MenuWidgetItem *redo_menu;
+    MenuWidgetItem *snap_to_zero_crossing_menu;
     MenuWidgetItem *always_show_tabs_menu;
+    MenuWidgetItem *perf_stats_menu;
     MenuWidgetItem *show_view_menu;
     MenuWidgetItem *toggle_playback_menu;
     MenuWidgetItem *restart_playback_menu;
     MenuWidgetItem *stop_playback_menu;
+    // the menu item for each KeyBindingAction, or nullptr for the ones
+    // that are not in a menu
+    MenuWidgetItem *action_menus[KeyBindingActionCount];
+    TrackEditorWidget *track_editor;
     bool always_show_tabs;
     DockAreaWidget* dock_area;
     TextWidget *fps_widget;
+    // the message of the open error modal, or nullptr
+    TextWidget *error_text;
+    // whether the overlay has the layout guide on it
+    bool layout_guide_shown;
     List<EditorPane *> all_panes;
     MenuWidget *menu_widget;
 };
 
+struct GenesisEditorOptions {
+    // restore the play head and scroll positions of the open project from the
+    // settings file
+    bool restore_session;
+    // font size of all text in the user interface. 0 means the default
+    int font_size;
+    // whole number scale of the user interface, see Gui::set_integer_scale
+    int scale;
+    // see Gui::set_high_contrast
+    bool high_contrast;
+    // see Gui::set_msaa_samples
+    int msaa_samples;
+    // audio files to import into the open project on startup
+    List<ByteBuffer> import_paths;
+};
+
 class GenesisEditor {
 public:
-    GenesisEditor();
+    GenesisEditor(const GenesisEditorOptions *options);
     ~GenesisEditor();
 
     void exec();
@@ -56,6 +85,7 @@ public:
     GenesisContext *genesis_context;
 
     ResourceBundle *resource_bundle;
+    FontLibrary *font_library;
     Gui *gui;
 
     List<EditorWindow *> windows;
@@ -66,6 +96,12 @@ public:
 
     int underrun_count;
 
+    // show frame time and glyph cache size next to the fps
+    bool show_perf_stats;
+
+    // current shortcut for each command, from the settings file
+    KeySequence key_bindings[KeyBindingActionCount];
+
     bool on_key_event(GuiWindow *window, const KeyEvent *event);
     bool on_text_event(GuiWindow *window, const TextInputEvent *event);
 
@@ -73,9 +109,21 @@ public:
     void destroy_audio_edit_widget();
 
     void refresh_menu_state();
+    void toggle_perf_stats();
+    // reloads the GENESIS_FONT file, see FontLibrary::reload_font
+    void reload_font();
+    void toggle_snap_to_zero_crossing();
+    // returns whether commands were added to the settings file
+    bool load_key_bindings();
+    void reset_key_bindings();
     int window_index(EditorWindow *window);
     void close_window(EditorWindow *window);
     void close_others(EditorWindow *window);
+    int import_audio_file(const ByteBuffer &path);
+    void import_dropped_files(EditorWindow *editor_window);
+    // shows message in a modal over the window until it is dismissed
+    void show_error(EditorWindow *editor_window, const ByteBuffer &message);
+    void destroy_error(EditorWindow *editor_window);
 
     void do_undo();
     void do_redo();
@@ -96,6 +144,13 @@ public:
     void restart_playback();
     void stop_playback();
 
+    // markers are bookmarks in the open project, saved with the session
+    void add_marker(double whole_note_pos, const String &name);
+    const List<SettingsFileMarker> &markers() const;
+    void add_marker_at_play_head();
+    void jump_to_previous_marker();
+    void jump_to_next_marker();
+
     GenesisEditor(const GenesisEditor &copy) = delete;
     GenesisEditor &operator=(const GenesisEditor &copy) = delete;
 };
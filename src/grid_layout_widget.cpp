@@ -7,7 +7,7 @@ void GridLayoutWidget::draw(const glm::mat4 &projection) {
         for (int col = 0; col < cols(); col += 1) {
             Widget *widget = r->at(col).widget;
             if (widget && widget->is_visible)
-                widget->draw(projection);
+                widget->draw_clipped(projection);
         }
     }
 }
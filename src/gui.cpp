@@ -2,15 +2,7 @@
 #include "os.hpp"
 #include "audio_graph.hpp"
 #include "render_job.hpp"
-
-uint32_t hash_int(const int &x) {
-    return (uint32_t) x;
-}
-
-static void ft_ok(FT_Error err) {
-    if (err)
-        panic("freetype error");
-}
+#include "color.hpp"
 
 static void panic_on_glfw_error(int error, const char* description) {
     panic("GLFW error: %s", description);
@@ -51,7 +43,18 @@ static void midi_device_callback(void *userdata) {
     gui->events.trigger(EventMidiDeviceChange);
 }
 
-Gui::Gui(GenesisContext *context, ResourceBundle *resource_bundle) :
+// genesis events arrive on other threads. wake the main loop so that it
+// does not sit in glfwWaitEvents with them unhandled
+static void event_callback(void *userdata) {
+    glfwPostEmptyEvent();
+}
+
+static const double MAX_DELTA_TIME = 0.25;
+// how often windows redraw while nothing asks them to
+static const double IDLE_REDRAW_INTERVAL = 0.25;
+
+Gui::Gui(GenesisContext *context, ResourceBundle *resource_bundle, FontLibrary *font_library) :
+    font_library(font_library),
     _running(true),
     _focus_window(nullptr),
     _utility_window(create_utility_window()),
@@ -77,23 +80,36 @@ Gui::Gui(GenesisContext *context, ResourceBundle *resource_bundle) :
     img_null(_spritesheet.get_image_info("img/null.png")),
     img_play_head(_spritesheet.get_image_info("img/play_head.png")),
     _genesis_context(context),
+    default_font_size(12),
+    high_contrast(false),
+    show_focus_ring(true),
+    text_gamma_correct(false),
+    frame_start_time(0.0),
+    delta_time(0.0),
+    _redraw_generation(0),
+    _redraw_cond(ok_mem(os_cond_create())),
+    fixed_timestep(0.0),
     dragging(false),
     drag_data(nullptr),
     drag_window(nullptr)
 {
-
-    ft_ok(FT_Init_FreeType(&_ft_library));
-    _resource_bundle->get_file_buffer("font.ttf", _default_font_buffer);
-    ft_ok(FT_New_Memory_Face(_ft_library, (FT_Byte*)_default_font_buffer.raw(),
-                _default_font_buffer.length(), 0, &_default_font_face));
+    integer_scale = 1;
+    msaa_samples = 0;
+    // the utility window's context is current on this thread
+    glGetIntegerv(GL_MAX_SAMPLES, &max_msaa_samples);
+    text_coverage_gamma = 1.0f;
+    text_texture_min_size = 1;
+    text_texture_power_of_two = false;
 
     cursor_default = glfwCreateStandardCursor(GLFW_ARROW_CURSOR);
     cursor_ibeam = glfwCreateStandardCursor(GLFW_IBEAM_CURSOR);
     cursor_hresize = glfwCreateStandardCursor(GLFW_HRESIZE_CURSOR);
     cursor_vresize = glfwCreateStandardCursor(GLFW_VRESIZE_CURSOR);
+    cursor_hand = glfwCreateStandardCursor(GLFW_HAND_CURSOR);
 
     genesis_set_audio_device_callback(_genesis_context, audio_device_callback, this);
     genesis_set_midi_device_callback(_genesis_context, midi_device_callback, this);
+    genesis_set_event_callback(_genesis_context, event_callback, this);
 
     genesis_set_sound_backend_disconnect_callback(_genesis_context, sound_backend_disconnect_callback, this);
 
@@ -116,29 +132,42 @@ Gui::~Gui() {
 
     glfwDestroyCursor(cursor_default);
     glfwDestroyCursor(cursor_ibeam);
+    glfwDestroyCursor(cursor_hresize);
+    glfwDestroyCursor(cursor_vresize);
+    glfwDestroyCursor(cursor_hand);
 
-    auto it = _font_size_cache.entry_iterator();
-    for (;;) {
-        auto *entry = it.next();
-        if (!entry)
-            break;
-        FontSize *font_size_object = entry->value;
-        destroy(font_size_object, 1);
-    }
-
-    FT_Done_Face(_default_font_face);
-    FT_Done_FreeType(_ft_library);
+    os_cond_destroy(_redraw_cond);
 }
 
 void Gui::exec() {
     os_mutex_unlock(gui_mutex);
     fps = 60.0;
+    frame_time = 1.0 / fps;
     double last_time = os_get_time();
+    frame_start_time = last_time;
     while (_running) {
         os_mutex_lock(gui_mutex);
+        // nothing changes on screen until there is input or a genesis event.
+        // the wait holds gui_mutex, so first let every window draw
+        if (needs_continuous_redraw() || !windows_drawn())
+            glfwPollEvents();
+        else
+            glfwWaitEvents();
         genesis_flush_events(_genesis_context);
-        glfwPollEvents();
         events.trigger(EventFlushEvents);
+
+        double now = os_get_time();
+        delta_time = min(now - frame_start_time, MAX_DELTA_TIME);
+        frame_start_time = now;
+        events.trigger(EventFrameUpdate);
+        if (fixed_timestep > 0.0) {
+            int step_count = _fixed_timestep_steps.advance(fixed_timestep, delta_time);
+            for (int i = 0; i < step_count; i += 1)
+                events.trigger(EventFixedUpdate);
+        }
+
+        if (_redraw_state.end_iteration())
+            request_redraw();
         os_mutex_unlock(gui_mutex);
 
         _utility_window->draw();
@@ -148,17 +177,48 @@ void Gui::exec() {
         last_time = this_time;
         double this_fps = 1.0 / delta;
         fps = fps * 0.90 + this_fps * 0.10;
+        frame_time = frame_time * 0.90 + delta * 0.10;
     }
     os_mutex_lock(gui_mutex);
 }
 
-FontSize *Gui::get_font_size(int font_size) {
-    auto *entry = _font_size_cache.maybe_get(font_size);
-    if (entry)
-        return entry->value;
-    FontSize *font_size_object = create<FontSize>(_default_font_face, font_size);
-    _font_size_cache.put(font_size, font_size_object);
-    return font_size_object;
+void Gui::request_redraw() {
+    _redraw_generation += 1;
+    os_cond_broadcast(_redraw_cond, gui_mutex);
+}
+
+bool Gui::windows_drawn() const {
+    for (int i = 0; i < _window_list.length(); i += 1) {
+        if (_window_list.at(i)->_drawn_redraw_generation != _redraw_generation)
+            return false;
+    }
+    return true;
+}
+
+void Gui::wait_for_redraw(GuiWindow *window) {
+    OsMutexLocker locker(gui_mutex);
+    if (window->running && window->_drawn_redraw_generation == _redraw_generation)
+        os_cond_timed_wait(_redraw_cond, gui_mutex, IDLE_REDRAW_INTERVAL);
+}
+
+void Gui::set_integer_scale(int scale) {
+    assert(scale >= 1);
+    integer_scale = scale;
+    for (int i = 0; i < _window_list.length(); i += 1) {
+        GuiWindow *window = _window_list.at(i);
+        if (window != _utility_window)
+            window->set_scale(scale);
+    }
+}
+
+void Gui::set_msaa_samples(int samples) {
+    assert(samples >= 0);
+    if (samples > max_msaa_samples) {
+        fprintf(stderr, "%d samples of anti-aliasing requested, the driver supports %d\n",
+                samples, max_msaa_samples);
+        samples = max_msaa_samples;
+    }
+    msaa_samples = samples;
 }
 
 void Gui::draw_image(GuiWindow *window, const SpritesheetImage *img, const glm::mat4 &mvp) {
@@ -171,6 +231,42 @@ void Gui::draw_image_color(GuiWindow *window, const SpritesheetImage *img,
     _spritesheet.draw_color(window, img, mvp, color);
 }
 
+void Gui::fill_rounded_rect(const glm::vec4 &fill_color, const glm::vec4 &border_color,
+        float radius, float border_width, int w, int h, const glm::mat4 &mvp)
+{
+    ShaderProgramManager *spm = &_shader_program_manager;
+    spm->rounded_rect_program.bind();
+
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_mvp, mvp);
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_width, (float)w);
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_height, (float)h);
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_radius,
+            clamp(0.0f, radius, min(w, h) / 2.0f));
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_border_width, border_width);
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_fill_color, fill_color);
+    spm->rounded_rect_program.set_uniform(spm->rounded_rect_uniform_border_color, border_color);
+
+    glBindBuffer(GL_ARRAY_BUFFER, _static_geometry._rect_2d_vertex_buffer);
+    glEnableVertexAttribArray(spm->rounded_rect_attrib_position);
+    glVertexAttribPointer(spm->rounded_rect_attrib_position, 3, GL_FLOAT, GL_FALSE, 0, NULL);
+
+    glDrawArrays(GL_TRIANGLE_STRIP, 0, 4);
+}
+
+void Gui::set_high_contrast(bool new_high_contrast) {
+    if (high_contrast == new_high_contrast)
+        return;
+    high_contrast = new_high_contrast;
+    color_high_contrast = high_contrast;
+    if (high_contrast) {
+        default_font_size += 2;
+        text_coverage_gamma = 1.5f;
+    } else {
+        default_font_size -= 2;
+        text_coverage_gamma = 1.0f;
+    }
+}
+
 GuiWindow *Gui::create_window(int left, int top, int width, int height) {
     return create_generic_window(true, left, top, width, height);
 }
@@ -6,7 +6,7 @@
 #include "list.hpp"
 #include "glm.hpp"
 #include "hash_map.hpp"
-#include "font_size.hpp"
+#include "font_library.hpp"
 #include "resource_bundle.hpp"
 #include "spritesheet.hpp"
 #include "gui_window.hpp"
@@ -14,10 +14,11 @@
 #include "glfw.hpp"
 #include "event_dispatcher.hpp"
 #include "drag_event.hpp"
+#include "redraw_state.hpp"
+#include "fixed_timestep.hpp"
 
 struct RenderJob;
 
-uint32_t hash_int(const int &x);
 
 class GlobalGlfwContext {
 public:
@@ -30,7 +31,9 @@ private:
 
 class Gui {
 public:
-    Gui(GenesisContext *context, ResourceBundle *resource_bundle);
+    // font_library may be shared with other Gui instances and must outlive
+    // this one
+    Gui(GenesisContext *context, ResourceBundle *resource_bundle, FontLibrary *font_library);
     ~Gui();
 
     void exec();
@@ -38,11 +41,15 @@ public:
     GuiWindow *create_window(int left, int top, int width, int height);
     void destroy_window(GuiWindow *window);
 
-    FontSize *get_font_size(int font_size);
+    // faces and glyph metrics, see FontLibrary
+    FontLibrary *font_library;
 
     void draw_image(GuiWindow *window, const SpritesheetImage *img, const glm::mat4 &mvp);
     void draw_image_color(GuiWindow *window, const SpritesheetImage *img,
             const glm::mat4 &mvp, const glm::vec4 &color);
+    // mvp maps the unit square to the rectangle, which is w by h pixels
+    void fill_rounded_rect(const glm::vec4 &fill_color, const glm::vec4 &border_color,
+            float radius, float border_width, int w, int h, const glm::mat4 &mvp);
 
     void start_drag(GuiWindow *gui_window, const MouseEvent *event, DragData *drag_data);
     void end_drag();
@@ -69,15 +76,9 @@ public:
     GLFWcursor* cursor_default;
     GLFWcursor* cursor_hresize;
     GLFWcursor* cursor_vresize;
-
-    FT_Library _ft_library;
-    FT_Face _default_font_face;
-
-    // key is font size
-    HashMap<int, FontSize *, hash_int> _font_size_cache;
+    GLFWcursor* cursor_hand;
 
     ResourceBundle *_resource_bundle;
-    ByteBuffer _default_font_buffer;
 
     List<RenderJob *> render_jobs;
 
@@ -108,7 +109,83 @@ public:
 
     EventDispatcher events;
 
+    // font size new labels start out with
+    int default_font_size;
+
+    // switches to the high contrast colors, makes text 2 sizes larger than
+    // default_font_size and thickens it. widgets copy their colors when they
+    // are created, so call this before creating any windows.
+    void set_high_contrast(bool high_contrast);
+    bool high_contrast;
+
+    // outline the focused widget, if it is focusable. default true
+    bool show_focus_ring;
+
+    // draw every normal window at this whole multiple of its logical size
+    // with nearest neighbor magnification, so HiDPI screens stay sharp
+    void set_integer_scale(int scale);
+    int integer_scale;
+
+    // multisample anti-aliasing samples per pixel for normal windows created
+    // after this, 0 for none. counts above max_msaa_samples, what the driver
+    // supports, are lowered to it. each GuiWindow's msaa_samples tells what
+    // it actually got. default 0
+    void set_msaa_samples(int samples);
+    int msaa_samples;
+    int max_msaa_samples;
+
+    // adjust glyph coverage so text blends as if in linear space rather than
+    // sRGB, keeping light-on-dark and dark-on-light text the same weight
+    bool text_gamma_correct;
+    // glyph coverage is raised to 1 / text_coverage_gamma before blending.
+    // above 1 thickens text, which helps legibility at small sizes. default 1
+    float text_coverage_gamma;
+    // label textures are allocated at least text_texture_min_size pixels on
+    // each side and, with text_texture_power_of_two, rounded up to a power of
+    // 2, for older drivers that handle other sizes slowly or not at all. the
+    // text stays in the top left of the texture. defaults 1 and false, exact
+    // sizes. labels pick changes up on their next update().
+    int text_texture_min_size;
+    bool text_texture_power_of_two;
+
     double fps;
+    // seconds per frame, smoothed the same way as fps
+    double frame_time;
+
+    // the frame clock, in seconds. frame_start_time is os_get_time() when the
+    // current frame started and delta_time is how long since the previous
+    // one, capped at a quarter second so that a stall does not jump
+    // animations ahead. handlers of EventFrameUpdate scale their motion by
+    // delta_time.
+    double frame_start_time;
+    double delta_time;
+    // windows redraw every frame while something is animating, and otherwise
+    // only after input, request_redraw() or an idle interval, which keeps
+    // state that changed without asking from looking stale for long.
+    // call keep_animating() each main loop iteration, from EventFlushEvents
+    // or EventFrameUpdate, while something on screen changes every frame.
+    void request_redraw();
+    void keep_animating() {
+        _redraw_state.keep_animating();
+    }
+    // whether anything called keep_animating() in the last iteration. while
+    // it is false the main loop blocks until there is input or an event
+    bool needs_continuous_redraw() const {
+        return _redraw_state.needs_continuous_redraw();
+    }
+    // called by window threads between frames
+    void wait_for_redraw(GuiWindow *window);
+    // whether every window drew since the last request_redraw()
+    bool windows_drawn() const;
+    RedrawState _redraw_state;
+    int _redraw_generation;
+    OsCond *_redraw_cond;
+
+    // when above 0, EventFixedUpdate is triggered as many times each frame as
+    // fit in the time that passed, for updates that must not depend on the
+    // frame rate. default 0, off
+    double fixed_timestep;
+    FixedTimestep _fixed_timestep_steps;
 
     bool dragging;
     DragData *drag_data;
@@ -5,6 +5,9 @@
 #include "debug_gl.hpp"
 #include "menu_widget.hpp"
 #include "os.hpp"
+#include "color.hpp"
+#include "overlay.hpp"
+#include "modal_input.hpp"
 
 // how many pixels user must drag for drag to start
 static const int DRAG_DIST = 4;
@@ -15,54 +18,85 @@ static void run(void *arg) {
 
     while (gui_window->running) {
         gui_window->draw();
+        gui_window->gui->wait_for_redraw(gui_window);
     }
 
     gui_window->teardown_context();
 }
 
+// the depth range of _projection. transform2d places everything at z = 0
+// and depth testing is off, so these only need to contain 0
+static const float PROJECTION_NEAR = -1.0f;
+static const float PROJECTION_FAR = 1.0f;
+
 static void handle_new_size(GuiWindow *gui_window, int width, int height) {
-    gui_window->_width = width;
-    gui_window->_height = height;
-    gui_window->_projection = glm::ortho(0.0f, (float)width, (float)height, 0.0f);
+    gui_window->_framebuffer_width = width;
+    gui_window->_framebuffer_height = height;
+    gui_window->_width = width / gui_window->_scale;
+    gui_window->_height = height / gui_window->_scale;
+    gui_window->_projection = glm::ortho(0.0f, (float)gui_window->_width, (float)gui_window->_height, 0.0f,
+            PROJECTION_NEAR, PROJECTION_FAR);
+}
+
+// input can change anything on screen, so every event redraws the windows
+static GuiWindow *window_for_event(GLFWwindow *window) {
+    GuiWindow *gui_window = static_cast<GuiWindow*>(glfwGetWindowUserPointer(window));
+    gui_window->gui->request_redraw();
+    return gui_window;
 }
 
 static void static_window_pos_callback(GLFWwindow* window, int left, int top) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->window_pos_callback(left, top);
+    return window_for_event(window)->window_pos_callback(left, top);
 }
 
 static void static_window_close_callback(GLFWwindow* window) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->window_close_callback();
+    return window_for_event(window)->window_close_callback();
 }
 
 static void static_window_iconify_callback(GLFWwindow* window, int iconified) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->window_iconify_callback(iconified);
+    return window_for_event(window)->window_iconify_callback(iconified);
 }
 static void static_framebuffer_size_callback(GLFWwindow* window, int width, int height) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->framebuffer_size_callback(width, height);
+    return window_for_event(window)->framebuffer_size_callback(width, height);
 }
 static void static_key_callback(GLFWwindow* window, int key, int scancode, int action, int mods) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->key_callback(key, scancode, action, mods);
+    return window_for_event(window)->key_callback(key, scancode, action, mods);
 }
 static void static_charmods_callback(GLFWwindow* window, unsigned int codepoint, int mods) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->charmods_callback(codepoint, mods);
+    return window_for_event(window)->charmods_callback(codepoint, mods);
 }
 static void static_cursor_pos_callback(GLFWwindow* window, double xpos, double ypos) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->cursor_pos_callback(xpos, ypos);
+    return window_for_event(window)->cursor_pos_callback(xpos, ypos);
 }
 static void static_window_size_callback(GLFWwindow* window, int width, int height) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->window_size_callback(width, height);
+    return window_for_event(window)->window_size_callback(width, height);
 }
 static void static_mouse_button_callback(GLFWwindow* window, int button, int action, int mods) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->mouse_button_callback(button, action, mods);
+    return window_for_event(window)->mouse_button_callback(button, action, mods);
 }
 static void static_scroll_callback(GLFWwindow* window, double xoffset, double yoffset) {
-    return static_cast<GuiWindow*>(glfwGetWindowUserPointer(window))->scroll_callback(xoffset, yoffset);
+    return window_for_event(window)->scroll_callback(xoffset, yoffset);
+}
+static void static_drop_callback(GLFWwindow* window, int count, const char **paths) {
+    return window_for_event(window)->drop_callback(count, paths);
 }
 
 GuiWindow::GuiWindow(Gui *gui, bool is_normal_window, int left, int top, int width, int height) :
     _userdata(nullptr),
+    drawn_widget_count(0),
+    culled_widget_count(0),
+    _frame_drawn_widget_count(0),
+    _frame_culled_widget_count(0),
+    _frame_index(0),
+    _drawn_redraw_generation(-1),
     gui(gui),
+    _cursor(nullptr),
+    // the utility window is created before Gui has a scale
+    _scale(is_normal_window ? gui->integer_scale : 1),
+    msaa_samples(0),
     _mouse_over_widget(nullptr),
+    _next_hit_region_id(0),
+    _hover_hit_region_id(-1),
     _focus_widget(nullptr),
     menu_widget(nullptr),
     _is_iconified(false),
@@ -74,6 +108,11 @@ GuiWindow::GuiWindow(Gui *gui, bool is_normal_window, int left, int top, int wid
     running(true),
     main_widget(nullptr),
     context_menu(nullptr),
+    modal_widget(nullptr),
+    modal_on_dismiss(nullptr),
+    modal_userdata(nullptr),
+    modal_backdrop_color(color_modal_backdrop()),
+    focus_ring_color(color_focus_ring()),
     is_maximized(false),
     drag_widget(nullptr)
 {
@@ -82,12 +121,14 @@ GuiWindow::GuiWindow(Gui *gui, bool is_normal_window, int left, int top, int wid
         glfwWindowHint(GLFW_VISIBLE, GL_TRUE);
         glfwWindowHint(GLFW_DECORATED, GL_TRUE);
         glfwWindowHint(GLFW_FOCUSED, GL_TRUE);
+        glfwWindowHint(GLFW_SAMPLES, gui->msaa_samples);
         window = glfwCreateWindow(width, height, "genesis", NULL, gui->_utility_window->window);
     } else {
         glfwWindowHint(GLFW_RESIZABLE, GL_FALSE);
         glfwWindowHint(GLFW_VISIBLE, GL_FALSE);
         glfwWindowHint(GLFW_DECORATED, GL_FALSE);
         glfwWindowHint(GLFW_FOCUSED, GL_FALSE);
+        glfwWindowHint(GLFW_SAMPLES, 0);
         window = glfwCreateWindow(width, height, "genesis", NULL, NULL);
         is_visible = false;
     }
@@ -95,6 +136,8 @@ GuiWindow::GuiWindow(Gui *gui, bool is_normal_window, int left, int top, int wid
         panic("unable to create window");
     glfwSetWindowUserPointer(window, this);
 
+    overlay = create<Overlay>(this);
+
     glfwSetWindowPos(window, left, top);
 
     int window_size_width, window_size_height;
@@ -120,6 +163,7 @@ GuiWindow::GuiWindow(Gui *gui, bool is_normal_window, int left, int top, int wid
     glfwSetCursorPosCallback(window, static_cursor_pos_callback);
     glfwSetMouseButtonCallback(window, static_mouse_button_callback);
     glfwSetScrollCallback(window, static_scroll_callback);
+    glfwSetDropCallback(window, static_drop_callback);
 
 
     int err;
@@ -139,6 +183,7 @@ GuiWindow::~GuiWindow() {
         teardown_context();
     } else {
         running = false;
+        os_cond_broadcast(gui->_redraw_cond, gui->gui_mutex);
 
         os_mutex_unlock(gui->gui_mutex);
         os_thread_destroy(thread);
@@ -147,6 +192,7 @@ GuiWindow::~GuiWindow() {
 
     if (main_widget)
         destroy(main_widget, 1);
+    destroy(overlay, 1);
 
     glfwDestroyWindow(window);
 }
@@ -163,11 +209,18 @@ void GuiWindow::setup_context() {
 
     glClearColor(0.3, 0.3, 0.3, 1.0);
 
+    // layering is by draw order alone, which is what blending translucent
+    // widgets needs anyway. see draw()
+    glDisable(GL_DEPTH_TEST);
     glEnable(GL_BLEND);
-    glBlendFunc(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);
+    set_blend_mode(BlendModeNormal);
 
     glPixelStorei(GL_UNPACK_ALIGNMENT, 1);
 
+    // GLFW_SAMPLES is only a hint, the platform picks the closest
+    // framebuffer it has
+    glGetIntegerv(GL_SAMPLES, &msaa_samples);
+
     assert_no_gl_error();
 
     viewport_update_queued = true;
@@ -183,23 +236,62 @@ void GuiWindow::window_iconify_callback(int iconified) {
 }
 
 void GuiWindow::draw() {
-    if (viewport_update_queued.exchange(false))
-        glViewport(0, 0, _width, _height);
+    if (viewport_update_queued.exchange(false)) {
+        // keep the logical area pinned to the top left when the framebuffer
+        // is not a multiple of the scale
+        glViewport(0, logical_to_framebuffer_y(_height),
+                logical_to_framebuffer(_width), logical_to_framebuffer(_height));
+    }
 
     glClear(GL_COLOR_BUFFER_BIT|GL_DEPTH_BUFFER_BIT|GL_STENCIL_BUFFER_BIT);
 
     {
         OsMutexLocker locker(gui->gui_mutex);
 
+        _drawn_redraw_generation = gui->_redraw_generation;
+        _cull_left = 0;
+        _cull_top = 0;
+        _cull_right = _width;
+        _cull_bottom = _height;
+        _frame_drawn_widget_count = 0;
+        _frame_culled_widget_count = 0;
+
+        // painter's order: each layer covers the ones drawn before it
         if (main_widget && main_widget->is_visible)
-            main_widget->draw(_projection);
+            main_widget->draw_clipped(_projection);
+        if (modal_widget) {
+            fill_rect(modal_backdrop_color, 0, 0, _width, _height);
+            modal_widget->draw_clipped(_projection);
+        }
+        draw_focus_ring();
         if (context_menu && context_menu->is_visible)
-            context_menu->draw(_projection);
+            context_menu->draw_clipped(_projection);
+        overlay->draw(_projection);
 
+        drawn_widget_count = _frame_drawn_widget_count;
+        culled_widget_count = _frame_culled_widget_count;
+        _frame_index += 1;
     }
     glfwSwapBuffers(window);
 }
 
+static const int FOCUS_RING_WIDTH = 2;
+
+void GuiWindow::draw_focus_ring() {
+    if (!gui->show_focus_ring || !_focus_widget || !_focus_widget->focusable())
+        return;
+    for (Widget *widget = _focus_widget; widget; widget = widget->parent_widget) {
+        if (!widget->is_visible)
+            return;
+    }
+    // just outside the widget, so it does not cover the content
+    int w = _focus_widget->width + FOCUS_RING_WIDTH * 2;
+    int h = _focus_widget->height + FOCUS_RING_WIDTH * 2;
+    glm::mat4 mvp = _projection * _focus_widget->transform2d(
+            -FOCUS_RING_WIDTH, -FOCUS_RING_WIDTH, w, h);
+    fill_rounded_rect(glm::vec4(0.0f), focus_ring_color, 3.0f, FOCUS_RING_WIDTH, w, h, mvp);
+}
+
 void GuiWindow::layout_main_widget() {
     if (main_widget) {
         main_widget->left = 0;
@@ -208,6 +300,56 @@ void GuiWindow::layout_main_widget() {
         main_widget->height = _height;
         main_widget->on_resize();
     }
+    layout_modal_widget();
+}
+
+void GuiWindow::layout_modal_widget() {
+    if (!modal_widget)
+        return;
+    modal_widget->width = min(max(modal_widget->min_width(), 1), _width);
+    modal_widget->height = min(max(modal_widget->min_height(), 1), _height);
+    modal_widget->left = (_width - modal_widget->width) / 2;
+    modal_widget->top = (_height - modal_widget->height) / 2;
+    modal_widget->on_resize();
+}
+
+void GuiWindow::show_modal(Widget *widget, void (*on_dismiss)(void *userdata), void *userdata) {
+    assert(!modal_widget);
+    destroy_context_menu();
+
+    modal_widget = widget;
+    modal_on_dismiss = on_dismiss;
+    modal_userdata = userdata;
+    widget->parent_widget = nullptr;
+    layout_modal_widget();
+
+    if (_mouse_over_widget) {
+        Widget *old_mouse_over_widget = _mouse_over_widget;
+        _mouse_over_widget = nullptr;
+        MouseEvent mouse_event = {};
+        old_mouse_over_widget->on_mouse_out(&mouse_event);
+    }
+    set_cursor_default();
+    set_focus_widget(widget);
+}
+
+void GuiWindow::close_modal() {
+    if (!modal_widget)
+        return;
+    if (_mouse_over_widget == modal_widget)
+        _mouse_over_widget = nullptr;
+    modal_widget = nullptr;
+    modal_on_dismiss = nullptr;
+    modal_userdata = nullptr;
+    set_focus_widget(main_widget);
+}
+
+void GuiWindow::dismiss_modal() {
+    void (*on_dismiss)(void *userdata) = modal_on_dismiss;
+    void *userdata = modal_userdata;
+    close_modal();
+    if (on_dismiss)
+        on_dismiss(userdata);
 }
 
 void GuiWindow::framebuffer_size_callback(int width, int height) {
@@ -218,6 +360,15 @@ void GuiWindow::framebuffer_size_callback(int width, int height) {
     viewport_update_queued = true;
 }
 
+void GuiWindow::set_scale(int scale) {
+    _scale = scale;
+    handle_new_size(this, _framebuffer_width, _framebuffer_height);
+
+    layout_main_widget();
+
+    viewport_update_queued = true;
+}
+
 void GuiWindow::set_main_widget(Widget *widget) {
     main_widget = widget;
     widget->parent_widget = nullptr;
@@ -264,6 +415,13 @@ void GuiWindow::key_callback(int key, int scancode, int action, int mods) {
         event_target = event_target->parent_widget;
     }
 
+    if (modal_widget) {
+        // menu shortcuts would act on what is behind the modal
+        if (key_event.action == KeyActionDown && key_event.virt_key == VirtKeyEscape)
+            dismiss_modal();
+        return;
+    }
+
     if (menu_widget) {
         if (menu_widget->on_key_event(&key_event))
             return;
@@ -293,9 +451,16 @@ int GuiWindow::get_modifiers() {
     return mod_shift|mod_control|mod_alt|mod_super;
 }
 
+void GuiWindow::screen_to_logical(double screen_x, double screen_y, int *x, int *y) const {
+    double framebuffer_x = screen_x * _framebuffer_width / (double)_client_width;
+    double framebuffer_y = screen_y * _framebuffer_height / (double)_client_height;
+    *x = (int)floor(framebuffer_x / _scale);
+    *y = (int)floor(framebuffer_y / _scale);
+}
+
 void GuiWindow::cursor_pos_callback(double xpos, double ypos) {
-    int x = (xpos / (double)_client_width) * _width;
-    int y = (ypos / (double)_client_height) * _height;
+    int x, y;
+    screen_to_logical(xpos, ypos, &x, &y);
 
     bool left = (glfwGetMouseButton(window, GLFW_MOUSE_BUTTON_LEFT) == GLFW_PRESS);
     bool middle = (glfwGetMouseButton(window, GLFW_MOUSE_BUTTON_MIDDLE) == GLFW_PRESS);
@@ -329,8 +494,8 @@ void GuiWindow::mouse_button_callback(int button, int action, int mods) {
     }
     double xpos, ypos;
     glfwGetCursorPos(window, &xpos, &ypos);
-    int x = (xpos / (double)_client_width) * _width;
-    int y = (ypos / (double)_client_height) * _height;
+    int x, y;
+    screen_to_logical(xpos, ypos, &x, &y);
     bool left = (glfwGetMouseButton(window, GLFW_MOUSE_BUTTON_LEFT) == GLFW_PRESS);
     bool middle = (glfwGetMouseButton(window, GLFW_MOUSE_BUTTON_MIDDLE) == GLFW_PRESS);
     bool right = (glfwGetMouseButton(window, GLFW_MOUSE_BUTTON_RIGHT) == GLFW_PRESS);
@@ -362,8 +527,8 @@ void GuiWindow::scroll_callback(double xoffset, double yoffset) {
 
     double xpos, ypos;
     glfwGetCursorPos(window, &xpos, &ypos);
-    int x = (xpos / (double)_client_width) * _width;
-    int y = (ypos / (double)_client_height) * _height;
+    int x, y;
+    screen_to_logical(xpos, ypos, &x, &y);
     MouseWheelEvent wheel_event = {
         x - _mouse_over_widget->left,
         y - _mouse_over_widget->top,
@@ -375,6 +540,16 @@ void GuiWindow::scroll_callback(double xoffset, double yoffset) {
     _mouse_over_widget->on_mouse_wheel(&wheel_event);
 }
 
+void GuiWindow::drop_callback(int count, const char **paths) {
+    dropped_files.clear();
+    for (int i = 0; i < count; i += 1)
+        ok_or_panic(dropped_files.append(paths[i]));
+
+    events.trigger(EventWindowFilesDropped);
+
+    dropped_files.clear();
+}
+
 void GuiWindow::on_mouse_move(const MouseEvent *event) {
     if (gui->drag_data) {
         bool end_drag = (event->action == MouseActionUp && gui->drag_orig_event.button == event->button);
@@ -419,6 +594,9 @@ void GuiWindow::on_mouse_move(const MouseEvent *event) {
     // if we're pressing a mouse button, the mouse over widget gets the event
     bool pressing_any_btn = (event->buttons.left || event->buttons.middle || event->buttons.right);
 
+    if (dispatch_hit_regions(event, pressing_any_btn))
+        return;
+
     if (_mouse_over_widget) {
         int right = _mouse_over_widget->left + _mouse_over_widget->width;
         int bottom = _mouse_over_widget->top + _mouse_over_widget->height;
@@ -452,11 +630,148 @@ void GuiWindow::on_mouse_move(const MouseEvent *event) {
 
     assert(_mouse_over_widget == nullptr);
 
+    if (modal_widget) {
+        switch (modal_mouse_target(event, modal_widget->left, modal_widget->top,
+                    modal_widget->width, modal_widget->height))
+        {
+            case ModalMouseTargetModal:
+                try_mouse_move_event_on_widget(modal_widget, event);
+                break;
+            case ModalMouseTargetDismiss:
+                dismiss_modal();
+                break;
+            case ModalMouseTargetNone:
+                break;
+        }
+        return;
+    }
+
     if (main_widget)
         try_mouse_move_event_on_widget(main_widget, event);
 }
 
+int GuiWindow::add_hit_region(Widget *owner, int left, int top, int width, int height,
+        void (*on_click)(void *userdata),
+        void (*on_hover)(void *userdata, bool hovering), void *userdata)
+{
+    ok_or_panic(_hit_regions.add_one());
+    HitRegion *region = &_hit_regions.last();
+    region->id = _next_hit_region_id++;
+    region->owner = owner;
+    region->left = left;
+    region->top = top;
+    region->width = width;
+    region->height = height;
+    region->on_click = on_click;
+    region->on_hover = on_hover;
+    region->userdata = userdata;
+    return region->id;
+}
+
+int GuiWindow::hit_region_index(int id) const {
+    for (int i = 0; i < _hit_regions.length(); i += 1) {
+        if (_hit_regions.at(i).id == id)
+            return i;
+    }
+    panic("hit region not found");
+}
+
+GuiWindow::HitRegion *GuiWindow::find_hit_region(int id) {
+    for (int i = 0; i < _hit_regions.length(); i += 1) {
+        if (_hit_regions.at(i).id == id)
+            return &_hit_regions.at(i);
+    }
+    return nullptr;
+}
+
+void GuiWindow::set_hit_region_rect(int id, int left, int top, int width, int height) {
+    HitRegion *region = &_hit_regions.at(hit_region_index(id));
+    region->left = left;
+    region->top = top;
+    region->width = width;
+    region->height = height;
+}
+
+void GuiWindow::remove_hit_region(int id) {
+    int index = hit_region_index(id);
+    // keep the order, it is the stacking order
+    _hit_regions.remove_range(index, index + 1);
+    if (_hover_hit_region_id == id) {
+        _hover_hit_region_id = -1;
+        set_cursor_default();
+    }
+}
+
+int GuiWindow::hit_region_at(int x, int y) const {
+    for (int i = _hit_regions.length() - 1; i >= 0; i -= 1) {
+        const HitRegion *region = &_hit_regions.at(i);
+        if (region->owner && region->owner->drawn_frame != _frame_index - 1)
+            continue;
+        if (x >= region->left && y >= region->top &&
+            x < region->left + region->width && y < region->top + region->height)
+        {
+            return region->id;
+        }
+    }
+    return -1;
+}
+
+bool GuiWindow::dispatch_hit_regions(const MouseEvent *event, bool pressing_any_btn) {
+    // a widget that saw the button go down keeps the mouse until it is
+    // released, and a modal takes all input
+    int id = -1;
+    if (!modal_widget && !(pressing_any_btn && _mouse_over_widget))
+        id = hit_region_at(event->x, event->y);
+
+    if (id != _hover_hit_region_id) {
+        int old_id = _hover_hit_region_id;
+        _hover_hit_region_id = id;
+        if (old_id != -1) {
+            HitRegion *old_region = &_hit_regions.at(hit_region_index(old_id));
+            if (id == -1)
+                set_cursor_default();
+            if (old_region->on_hover)
+                old_region->on_hover(old_region->userdata, false);
+        }
+        // the old region's callback may have removed the new one, and with
+        // it reset _hover_hit_region_id
+        HitRegion *region = (_hover_hit_region_id == id) ? find_hit_region(id) : nullptr;
+        if (region) {
+            set_cursor_hand();
+            if (region->on_hover)
+                region->on_hover(region->userdata, true);
+        } else if (id != -1) {
+            _hover_hit_region_id = -1;
+            set_cursor_default();
+        }
+    }
+    // the callbacks may have removed the region
+    HitRegion *region = find_hit_region(_hover_hit_region_id);
+    if (!region)
+        return false;
+
+    if (_mouse_over_widget) {
+        MouseEvent mouse_event = *event;
+        mouse_event.x -= _mouse_over_widget->left;
+        mouse_event.y -= _mouse_over_widget->top;
+        Widget *old_mouse_over_widget = _mouse_over_widget;
+        _mouse_over_widget = nullptr;
+        old_mouse_over_widget->on_mouse_out(&mouse_event);
+        set_cursor_hand();
+    }
+
+    if (event->action == MouseActionDown && event->button == MouseButtonLeft) {
+        // and so may on_mouse_out
+        region = find_hit_region(_hover_hit_region_id);
+        if (region && region->on_click)
+            region->on_click(region->userdata);
+    }
+    return true;
+}
+
 void GuiWindow::remove_widget(Widget *widget) {
+    if (widget == modal_widget)
+        close_modal();
     if (widget == drag_widget)
         drag_widget = nullptr;
     if (widget == _mouse_over_widget)
@@ -465,6 +780,10 @@ void GuiWindow::remove_widget(Widget *widget) {
         _focus_widget = nullptr;
         set_focus_widget(main_widget);
     }
+    for (int i = _hit_regions.length() - 1; i >= 0; i -= 1) {
+        if (_hit_regions.at(i).owner == widget)
+            remove_hit_region(_hit_regions.at(i).id);
+    }
 }
 
 bool GuiWindow::forward_drag_event(Widget *widget, const DragEvent *event) {
@@ -592,6 +911,24 @@ void GuiWindow::fill_rect_gradient(const glm::vec4 &top_color, const glm::vec4 &
     glDrawArrays(GL_TRIANGLE_STRIP, 0, 4);
 }
 
+void GuiWindow::fill_rounded_rect(const glm::vec4 &fill_color, const glm::vec4 &border_color,
+        float radius, float border_width, int w, int h, const glm::mat4 &mvp)
+{
+    gui->fill_rounded_rect(fill_color, border_color, radius, border_width, w, h, mvp);
+}
+
+void GuiWindow::set_blend_mode(BlendMode blend_mode) {
+    switch (blend_mode) {
+        case BlendModeNormal:
+            glBlendFunc(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);
+            return;
+        case BlendModeAdditive:
+            glBlendFunc(GL_SRC_ALPHA, GL_ONE);
+            return;
+    }
+    panic("invalid blend mode");
+}
+
 void GuiWindow::fill_rect(const glm::vec4 &color, const glm::mat4 &mvp) {
     gui->_shader_program_manager._primitive_shader_program.bind();
 
@@ -631,20 +968,31 @@ void GuiWindow::draw_image(const SpritesheetImage *img, int x, int y, int w, int
     gui->draw_image(this, img, mvp);
 }
 
+void GuiWindow::set_cursor(GLFWcursor *cursor) {
+    if (cursor == _cursor)
+        return;
+    _cursor = cursor;
+    glfwSetCursor(window, cursor);
+}
+
 void GuiWindow::set_cursor_beam() {
-    glfwSetCursor(window, gui->cursor_ibeam);
+    set_cursor(gui->cursor_ibeam);
 }
 
 void GuiWindow::set_cursor_default() {
-    glfwSetCursor(window, gui->cursor_default);
+    set_cursor(gui->cursor_default);
 }
 
 void GuiWindow::set_cursor_hresize() {
-    glfwSetCursor(window, gui->cursor_hresize);
+    set_cursor(gui->cursor_hresize);
 }
 
 void GuiWindow::set_cursor_vresize() {
-    glfwSetCursor(window, gui->cursor_vresize);
+    set_cursor(gui->cursor_vresize);
+}
+
+void GuiWindow::set_cursor_hand() {
+    set_cursor(gui->cursor_hand);
 }
 
 void GuiWindow::set_clipboard_string(const String &str) {
@@ -18,6 +18,15 @@ struct SpritesheetImage;
 class MenuWidgetItem;
 class MenuWidget;
 class ContextMenuWidget;
+class Overlay;
+
+enum BlendMode {
+    // source over destination, weighted by source alpha
+    BlendModeNormal,
+    // source weighted by alpha is added to the destination, so overlapping
+    // draws get brighter
+    BlendModeAdditive,
+};
 
 class GuiWindow {
 public:
@@ -35,14 +44,33 @@ public:
     void set_cursor_default();
     void set_cursor_hresize();
     void set_cursor_vresize();
+    void set_cursor_hand();
+
+    // a window deals in three coordinate systems. screen coordinates are
+    // what GLFW reports the cursor position and window size in. framebuffer
+    // pixels are what GL renders to, and differ from screen coordinates on
+    // HiDPI displays. logical pixels are what widgets are laid out in. convert
+    // between them with these rather than inline.
+    void screen_to_logical(double screen_x, double screen_y, int *x, int *y) const;
+    int logical_to_framebuffer(int logical) const { return logical * _scale; }
+    // for glViewport and glScissor, which count from the bottom
+    int logical_to_framebuffer_y(int logical_y) const {
+        return _framebuffer_height - logical_y * _scale;
+    }
 
     bool try_mouse_move_event_on_widget(Widget *widget, const MouseEvent *event);
 
     void fill_rect(const glm::vec4 &color, const glm::mat4 &mvp);
     void fill_rect(const glm::vec4 &color, int x, int y, int w, int h);
+    void fill_rounded_rect(const glm::vec4 &fill_color, const glm::vec4 &border_color,
+            float radius, float border_width, int w, int h, const glm::mat4 &mvp);
     void draw_image(const SpritesheetImage *img, int x, int y, int w, int h);
     void fill_rect_gradient(const glm::vec4 &top_color, const glm::vec4 &bottom_color, const glm::mat4 &mvp);
 
+    // affects everything drawn afterwards. set it back to BlendModeNormal
+    // when done.
+    void set_blend_mode(BlendMode blend_mode);
+
     void set_clipboard_string(const String &str);
     String get_clipboard_string() const;
     bool clipboard_has_string() const;
@@ -54,17 +82,69 @@ public:
 
     void start_drag(const MouseEvent *event, DragData *drag_data);
 
+    // draws widget centered over the dimmed window and sends it all input
+    // until close_modal(). clicking outside of it or pressing escape closes
+    // it and calls on_dismiss, which is optional. the window does not take
+    // ownership of widget.
+    void show_modal(Widget *widget, void (*on_dismiss)(void *userdata), void *userdata);
+    void close_modal();
+
+    // a clickable rectangle in window coordinates, for small interactive
+    // areas that do not warrant a widget. hit regions get the mouse before
+    // any widget but after modals and context menus, and later regions are
+    // above earlier ones. on_click is called for a left button press and
+    // on_hover with whether the mouse entered or left. either may be null.
+    // with an owner, the region only counts while that widget was drawn in
+    // the last frame, so regions of a widget in a hidden tab stay inert.
+    // returns a handle for set_hit_region_rect and remove_hit_region.
+    int add_hit_region(Widget *owner, int left, int top, int width, int height,
+            void (*on_click)(void *userdata),
+            void (*on_hover)(void *userdata, bool hovering), void *userdata);
+    void set_hit_region_rect(int id, int left, int top, int width, int height);
+    void remove_hit_region(int id);
+
     void *_userdata;
     // index into Gui's list of windows
     int _gui_index;
 
+    // how many widgets the last frame drew, and how many it skipped because
+    // they lay entirely outside the window or the clip of an ancestor
+    int drawn_widget_count;
+    int culled_widget_count;
+    // while drawing, the area in logical pixels that a widget must overlap
+    // for Widget::draw_clipped() to draw it. narrowed by each clip.
+    int _cull_left;
+    int _cull_top;
+    int _cull_right;
+    int _cull_bottom;
+    int _frame_drawn_widget_count;
+    int _frame_culled_widget_count;
+    // frames drawn so far. widgets note the one they were last drawn in
+    int _frame_index;
+    // Gui::_redraw_generation when the last frame was drawn
+    int _drawn_redraw_generation;
+
     Gui *gui;
     GLFWwindow *window;
+    // the cursor last passed to glfwSetCursor, so that widgets can ask for
+    // a cursor on every mouse move without calling into the window system
+    GLFWcursor *_cursor;
     GLuint vertex_array_object;
 
-    // pixels
+    // logical pixels, which widgets are laid out in. each one covers
+    // _scale x _scale framebuffer pixels.
     int _width;
     int _height;
+    int _scale;
+
+    // samples per pixel of the framebuffer, 0 without multisampling. see
+    // Gui::set_msaa_samples. drawing that smooths its own edges in the
+    // shader can check this to see whether the hardware already does
+    int msaa_samples;
+
+    // pixels
+    int _framebuffer_width;
+    int _framebuffer_height;
 
     // screen coordinates
     int _client_width;
@@ -72,8 +152,30 @@ public:
     int client_left;
     int client_top;
 
+    // maps logical pixels to clip space with the origin at the top left
     glm::mat4 _projection;
     Widget *_mouse_over_widget;
+
+    struct HitRegion {
+        int id;
+        Widget *owner;
+        int left;
+        int top;
+        int width;
+        int height;
+        void (*on_click)(void *userdata);
+        void (*on_hover)(void *userdata, bool hovering);
+        void *userdata;
+    };
+    List<HitRegion> _hit_regions;
+    int _next_hit_region_id;
+    // id of the region under the mouse, or -1
+    int _hover_hit_region_id;
+    int hit_region_index(int id) const;
+    // nullptr instead of a panic for ids that were removed
+    HitRegion *find_hit_region(int id);
+    int hit_region_at(int x, int y) const;
+    bool dispatch_hit_regions(const MouseEvent *event, bool pressing_any_btn);
     Widget *_focus_widget;
     MenuWidget *menu_widget;
 
@@ -93,14 +195,30 @@ public:
 
     Widget *main_widget;
     ContextMenuWidget *context_menu;
+    // drawn last, above even context menus
+    Overlay *overlay;
+
+    Widget *modal_widget;
+    void (*modal_on_dismiss)(void *userdata);
+    void *modal_userdata;
+    glm::vec4 modal_backdrop_color;
+    glm::vec4 focus_ring_color;
 
     void layout_main_widget();
+    void draw_focus_ring();
+    void layout_modal_widget();
+    void dismiss_modal();
+    void set_scale(int scale);
     int get_modifiers();
     void on_mouse_move(const MouseEvent *event);
 
     bool is_maximized;
     Widget *drag_widget;
 
+    // paths of the files dropped onto the window from the OS.
+    // only valid during EventWindowFilesDropped
+    List<ByteBuffer> dropped_files;
+
     void window_iconify_callback(int iconified);
     void framebuffer_size_callback(int width, int height);
     void window_size_callback(int width, int height);
@@ -111,10 +229,12 @@ public:
     void mouse_button_callback(int button, int action, int mods);
     void scroll_callback(double xoffset, double yoffset);
     void window_pos_callback(int left, int top);
+    void drop_callback(int count, const char **paths);
 
     void setup_context();
     void teardown_context();
     void destroy_context_menu();
+    void set_cursor(GLFWcursor *cursor);
     void got_window_size(int width, int height);
     void got_window_pos(int left, int top);
     bool widget_is_menu(Widget *widget);
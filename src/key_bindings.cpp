@@ -0,0 +1,58 @@
+#include "key_bindings.hpp"
+
+const char *key_binding_action_name(KeyBindingAction action) {
+    switch (action) {
+        case KeyBindingActionExit: return "exit";
+        case KeyBindingActionUndo: return "undo";
+        case KeyBindingActionRedo: return "redo";
+        case KeyBindingActionSnapToZeroCrossing: return "snap_to_zero_crossing";
+        case KeyBindingActionTogglePlayback: return "toggle_playback";
+        case KeyBindingActionRestartPlayback: return "restart_playback";
+        case KeyBindingActionStopPlayback: return "stop_playback";
+        case KeyBindingActionAddMarker: return "add_marker";
+        case KeyBindingActionPreviousMarker: return "previous_marker";
+        case KeyBindingActionNextMarker: return "next_marker";
+        case KeyBindingActionNewWindow: return "new_window";
+        case KeyBindingActionCloseWindow: return "close_window";
+        case KeyBindingActionTogglePerfStats: return "toggle_perf_stats";
+        case KeyBindingActionOpenWiki: return "open_wiki";
+        case KeyBindingActionSeekBackward: return "seek_backward";
+        case KeyBindingActionSeekForward: return "seek_forward";
+        case KeyBindingActionReloadFont: return "reload_font";
+        case KeyBindingActionCount: break;
+    }
+    panic("invalid key binding action");
+}
+
+KeyBindingAction key_binding_action_from_name(const ByteBuffer &name) {
+    for (int i = 0; i < KeyBindingActionCount; i += 1) {
+        KeyBindingAction action = (KeyBindingAction)i;
+        if (ByteBuffer::compare(name, key_binding_action_name(action)) == 0)
+            return action;
+    }
+    return KeyBindingActionCount;
+}
+
+KeySequence key_binding_default(KeyBindingAction action) {
+    switch (action) {
+        case KeyBindingActionExit: return ctrl_shortcut(VirtKeyQ);
+        case KeyBindingActionUndo: return ctrl_shortcut(VirtKeyZ);
+        case KeyBindingActionRedo: return ctrl_shift_shortcut(VirtKeyZ);
+        case KeyBindingActionSnapToZeroCrossing: return no_shortcut();
+        case KeyBindingActionTogglePlayback: return shortcut(VirtKeySpace);
+        case KeyBindingActionRestartPlayback: return shortcut(VirtKeyEnter);
+        case KeyBindingActionStopPlayback: return shift_shortcut(VirtKeyEnter);
+        case KeyBindingActionAddMarker: return shortcut(VirtKeyM);
+        case KeyBindingActionPreviousMarker: return shortcut(VirtKeyLeftBracket);
+        case KeyBindingActionNextMarker: return shortcut(VirtKeyRightBracket);
+        case KeyBindingActionNewWindow: return no_shortcut();
+        case KeyBindingActionCloseWindow: return alt_shortcut(VirtKeyF4);
+        case KeyBindingActionTogglePerfStats: return shortcut(VirtKeyF3);
+        case KeyBindingActionOpenWiki: return shortcut(VirtKeyF1);
+        case KeyBindingActionSeekBackward: return shortcut(VirtKeyLeft);
+        case KeyBindingActionSeekForward: return shortcut(VirtKeyRight);
+        case KeyBindingActionReloadFont: return no_shortcut();
+        case KeyBindingActionCount: break;
+    }
+    panic("invalid key binding action");
+}
@@ -0,0 +1,35 @@
+#ifndef KEY_BINDINGS_HPP
+#define KEY_BINDINGS_HPP
+
+#include "key_event.hpp"
+
+// editor commands that can be bound to a key sequence. in the key_bindings
+// section of the settings file each one is named by key_binding_action_name
+enum KeyBindingAction {
+    KeyBindingActionExit,               // exit
+    KeyBindingActionUndo,               // undo
+    KeyBindingActionRedo,               // redo
+    KeyBindingActionSnapToZeroCrossing, // snap_to_zero_crossing
+    KeyBindingActionTogglePlayback,     // toggle_playback
+    KeyBindingActionRestartPlayback,    // restart_playback
+    KeyBindingActionStopPlayback,       // stop_playback
+    KeyBindingActionAddMarker,          // add_marker
+    KeyBindingActionPreviousMarker,     // previous_marker
+    KeyBindingActionNextMarker,         // next_marker
+    KeyBindingActionNewWindow,          // new_window
+    KeyBindingActionCloseWindow,        // close_window
+    KeyBindingActionTogglePerfStats,    // toggle_perf_stats
+    KeyBindingActionOpenWiki,           // open_wiki
+    KeyBindingActionSeekBackward,       // seek_backward
+    KeyBindingActionSeekForward,        // seek_forward
+    KeyBindingActionReloadFont,         // reload_font
+
+    KeyBindingActionCount,
+};
+
+const char *key_binding_action_name(KeyBindingAction action);
+// returns KeyBindingActionCount if the name is not recognized
+KeyBindingAction key_binding_action_from_name(const ByteBuffer &name);
+KeySequence key_binding_default(KeyBindingAction action);
+
+#endif
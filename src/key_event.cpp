@@ -148,3 +148,42 @@ String key_sequence_to_string(const KeySequence &seq) {
 bool key_sequence_match(const KeySequence &seq, const KeyEvent *event) {
     return (seq.modifiers == event->modifiers) && (seq.key == event->virt_key);
 }
+
+static bool strip_prefix(const char **str, const char *prefix) {
+    size_t len = strlen(prefix);
+    if (strncmp(*str, prefix, len) != 0)
+        return false;
+    *str += len;
+    return true;
+}
+
+bool key_sequence_from_string(const ByteBuffer &str, KeySequence *out) {
+    if (str.length() == 0) {
+        *out = no_shortcut();
+        return true;
+    }
+    const char *ptr = str.raw();
+    int modifiers = 0;
+    for (;;) {
+        if (strip_prefix(&ptr, "Ctrl+"))
+            modifiers |= KeyModControl;
+        else if (strip_prefix(&ptr, "Shift+"))
+            modifiers |= KeyModShift;
+        else if (strip_prefix(&ptr, "Alt+"))
+            modifiers |= KeyModAlt;
+        else if (strip_prefix(&ptr, "Super+"))
+            modifiers |= KeyModSuper;
+        else
+            break;
+    }
+    // space is the lowest key code and menu is the highest
+    for (int key = VirtKeySpace; key <= VirtKeyMenu; key += 1) {
+        VirtKey virt_key = (VirtKey)key;
+        ByteBuffer name = virt_key_to_string(virt_key).encode();
+        if (ByteBuffer::compare(name, "Unknown") != 0 && strcmp(name.raw(), ptr) == 0) {
+            *out = make_shortcut(modifiers, virt_key);
+            return true;
+        }
+    }
+    return false;
+}
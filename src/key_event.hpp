@@ -191,6 +191,8 @@ struct KeySequence {
 bool null_key_sequence(const KeySequence &seq);
 String key_sequence_to_string(const KeySequence &seq);
 bool key_sequence_match(const KeySequence &seq, const KeyEvent *event);
+// parses the output of key_sequence_to_string. an empty string is no_shortcut()
+bool key_sequence_from_string(const ByteBuffer &str, KeySequence *out);
 
 static inline KeySequence make_shortcut(int modifiers, VirtKey key) {
     return KeySequence{modifiers, key};
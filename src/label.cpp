@@ -11,9 +11,34 @@ Label::Label(Gui *gui) :
     _gui(gui),
     _width(0),
     _height(0),
-    _text("")
+    _text(""),
+    _vertical(false),
+    _oversample(1.0f),
+    _kerning(true),
+    _normalize(true),
+    _filter(LabelFilterLinear),
+    _have_background(false),
+    _background_padding(2),
+    _background_corner_radius(3.0f),
+    _have_selection(false),
+    _selection_start(0),
+    _selection_end(0),
+    _alloc_width(1),
+    _alloc_height(1),
+    _auto_fit(false),
+    _auto_fit_dirty(false),
+    _rendered_font_size(nullptr),
+    _rendered_font_generation(0),
+    _rendered_vertical(false),
+    _rendered_kerning(true),
+    _rendered_oversample(1.0f),
+    _rendered_texture_min_size(1),
+    _rendered_texture_power_of_two(false),
+    _metrics_font_size(nullptr),
+    _metrics_font_generation(0),
+    _metrics_kerning(true)
 {
-    set_font_size(12);
+    set_font_size(gui->default_font_size);
 
     glGenTextures(1, &_texture_id);
     glBindTexture(GL_TEXTURE_2D, _texture_id);
@@ -23,7 +48,9 @@ Label::Label(Gui *gui) :
     glTexParameteri(GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, GL_LINEAR);
 
     glGenBuffers(1, &_vertex_buffer);
-
+    glGenBuffers(1, &_tex_coord_buffer);
+    glGenBuffers(1, &_selection_vertex_buffer);
+    glGenBuffers(1, &_selection_tex_coord_buffer);
 
     // send dummy vertex data - real data happens at update()
     GLfloat vertexes[4][3] = {
@@ -35,18 +62,72 @@ Label::Label(Gui *gui) :
     glBindBuffer(GL_ARRAY_BUFFER, _vertex_buffer);
     glBufferData(GL_ARRAY_BUFFER, 4 * 3 * sizeof(GLfloat), vertexes, GL_DYNAMIC_DRAW);
 
+    GLfloat tex_coords[4][2] = {
+        {0, 0},
+        {0, 0},
+        {0, 0},
+        {0, 0},
+    };
+    glBindBuffer(GL_ARRAY_BUFFER, _tex_coord_buffer);
+    glBufferData(GL_ARRAY_BUFFER, 4 * 2 * sizeof(GLfloat), tex_coords, GL_DYNAMIC_DRAW);
+
     update();
 }
 
 Label::~Label() {
+    glDeleteBuffers(1, &_selection_tex_coord_buffer);
+    glDeleteBuffers(1, &_selection_vertex_buffer);
+    glDeleteBuffers(1, &_tex_coord_buffer);
     glDeleteBuffers(1, &_vertex_buffer);
     glDeleteTextures(1, &_texture_id);
 }
 
+static float luminance(const glm::vec4 &color) {
+    return 0.2126f * color[0] + 0.7152f * color[1] + 0.0722f * color[2];
+}
+
+// Blending in linear space makes the edges of light text brighter and the
+// edges of dark text lighter than blending in sRGB space does. Raising the
+// coverage to a power below 1 for light text and above 1 for dark text
+// approximates that without reading back the destination.
+static float coverage_exponent(bool gamma_correct, const glm::vec4 &color) {
+    static const float gamma = 1.8f;
+    if (!gamma_correct)
+        return 1.0f;
+    return gamma + (1.0f / gamma - gamma) * luminance(color);
+}
+
+void Label::get_background_rect(int &left, int &top, int &width, int &height) const {
+    left = -_background_padding;
+    top = -_background_padding;
+    width = _width + _background_padding * 2;
+    height = _height + _background_padding * 2;
+}
+
 void Label::draw(const glm::mat4 &mvp, const glm::vec4 &color) {
-    if (_text.length() == 0)
+    if (_letters.length() == 0 || _width == 0 || _height == 0)
         return;
 
+    if (_have_background) {
+        int left, top, width, height;
+        get_background_rect(left, top, width, height);
+        glm::mat4 bg_mvp = glm::scale(glm::translate(mvp, glm::vec3(left, top, 0.0f)),
+                glm::vec3(width, height, 1.0f));
+        _gui->fill_rounded_rect(_background_color, _background_color,
+                _background_corner_radius, 0.0f, width, height, bg_mvp);
+    }
+
+    if (_have_selection && !_vertical && _selection_start < _selection_end) {
+        draw_selection(mvp, color);
+        return;
+    }
+
+    draw_text(mvp, color, _vertex_buffer, _tex_coord_buffer, GL_TRIANGLE_STRIP, 0, 4);
+}
+
+void Label::draw_text(const glm::mat4 &mvp, const glm::vec4 &color, GLuint vertex_buffer,
+        GLuint tex_coord_buffer, GLenum mode, int first, int count)
+{
     _gui->_shader_program_manager._text_shader_program.bind();
 
     _gui->_shader_program_manager._text_shader_program.set_uniform(
@@ -55,21 +136,131 @@ void Label::draw(const glm::mat4 &mvp, const glm::vec4 &color) {
     _gui->_shader_program_manager._text_shader_program.set_uniform(
             _gui->_shader_program_manager._text_uniform_tex, 0);
 
+    _gui->_shader_program_manager._text_shader_program.set_uniform(
+            _gui->_shader_program_manager._text_uniform_coverage_exponent,
+            coverage_exponent(_gui->text_gamma_correct, color) / _gui->text_coverage_gamma);
+
     _gui->_shader_program_manager._text_shader_program.set_uniform(
             _gui->_shader_program_manager._text_uniform_mvp, mvp);
 
-    glBindBuffer(GL_ARRAY_BUFFER, _vertex_buffer);
+    glBindBuffer(GL_ARRAY_BUFFER, vertex_buffer);
     glEnableVertexAttribArray(_gui->_shader_program_manager._text_attrib_position);
     glVertexAttribPointer(_gui->_shader_program_manager._text_attrib_position, 3, GL_FLOAT, GL_FALSE, 0, NULL);
 
-    glBindBuffer(GL_ARRAY_BUFFER, _gui->_static_geometry._rect_2d_tex_coord_buffer);
+    glBindBuffer(GL_ARRAY_BUFFER, tex_coord_buffer);
     glEnableVertexAttribArray(_gui->_shader_program_manager._text_attrib_tex_coord);
     glVertexAttribPointer(_gui->_shader_program_manager._text_attrib_tex_coord, 2, GL_FLOAT, GL_FALSE, 0, NULL);
 
     glActiveTexture(GL_TEXTURE0);
     glBindTexture(GL_TEXTURE_2D, _texture_id);
+    LabelSampling sampling = label_sampling(_filter, _gui->integer_scale);
+    glTexParameteri(GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, sampling.nearest_min ? GL_NEAREST : GL_LINEAR);
+    glTexParameteri(GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, sampling.nearest_mag ? GL_NEAREST : GL_LINEAR);
+
+    glDrawArrays(mode, first, count);
+}
+
+// the x of the left edge of the letter at index on line, or of the end of
+// the line if index is past its last letter
+float Label::letter_edge(const LabelLine *line, int index) const {
+    if (index <= line->start)
+        return 0.0f;
+    if (index < line->end)
+        return _letters.at(index).left / _oversample;
+    const Letter *last_letter = &_letters.at(line->end - 1);
+    return (last_letter->left + last_letter->full_width) / _oversample;
+}
+
+// two triangles covering the rectangle, in the pixels the label is drawn
+// with, and the part of the texture under it
+void Label::add_text_quad(float left, float top, float right, float bottom) {
+    if (right <= left || bottom <= top)
+        return;
+    float tex_left = left * _oversample / _alloc_width;
+    float tex_top = top * _oversample / _alloc_height;
+    float tex_right = right * _oversample / _alloc_width;
+    float tex_bottom = bottom * _oversample / _alloc_height;
+    GLfloat vertexes[6][3] = {
+        {left, top, 0.0f},
+        {left, bottom, 0.0f},
+        {right, top, 0.0f},
+        {right, top, 0.0f},
+        {left, bottom, 0.0f},
+        {right, bottom, 0.0f},
+    };
+    GLfloat tex_coords[6][2] = {
+        {tex_left, tex_top},
+        {tex_left, tex_bottom},
+        {tex_right, tex_top},
+        {tex_right, tex_top},
+        {tex_left, tex_bottom},
+        {tex_right, tex_bottom},
+    };
+    for (int i = 0; i < 6; i += 1) {
+        for (int j = 0; j < 3; j += 1)
+            ok_or_panic(_selection_vertexes.append(vertexes[i][j]));
+        for (int j = 0; j < 2; j += 1)
+            ok_or_panic(_selection_tex_coords.append(tex_coords[i][j]));
+    }
+}
 
-    glDrawArrays(GL_TRIANGLE_STRIP, 0, 4);
+// each line is split at the edges of the selection. the highlight is filled
+// behind the selected piece, then the text is drawn piece by piece so that
+// no pixel is blended in both colors.
+void Label::draw_selection(const glm::mat4 &mvp, const glm::vec4 &color) {
+    float contrast = (luminance(_selection_color) > 0.5f) ? 0.0f : 1.0f;
+    glm::vec4 selected_color(contrast, contrast, contrast, color[3]);
+
+    _selection_vertexes.clear();
+    _selection_tex_coords.clear();
+    int unselected_count = 0;
+    // selected pieces are added on a second pass over the lines
+    for (int pass = 0; pass < 2; pass += 1) {
+        for (int line_index = 0; line_index < _lines.length(); line_index += 1) {
+            const LabelLine *line = &_lines.at(line_index);
+            int start = max(_selection_start, line->start);
+            int end = min(_selection_end, line->end);
+            bool takes_break = line->end < _letters.length() &&
+                _selection_start <= line->end && _selection_end > line->end;
+            float top = line->top;
+            float bottom = line->top + line->height;
+            if (start >= end && !takes_break) {
+                if (pass == 0)
+                    add_text_quad(0.0f, top, _width, bottom);
+                continue;
+            }
+            float sel_left = letter_edge(line, start);
+            float sel_right = takes_break ? _width : letter_edge(line, end);
+            if (pass == 0) {
+                add_text_quad(0.0f, top, sel_left, bottom);
+                add_text_quad(sel_right, top, _width, bottom);
+
+                float width = sel_right - sel_left;
+                float height = line->height;
+                glm::mat4 sel_mvp = glm::scale(glm::translate(mvp, glm::vec3(sel_left, top, 0.0f)),
+                        glm::vec3(width, height, 1.0f));
+                _gui->fill_rounded_rect(_selection_color, _selection_color,
+                        0.0f, 0.0f, width, height, sel_mvp);
+            } else {
+                add_text_quad(sel_left, top, sel_right, bottom);
+            }
+        }
+        if (pass == 0)
+            unselected_count = _selection_vertexes.length() / 3;
+    }
+    int vertex_count = _selection_vertexes.length() / 3;
+
+    glBindBuffer(GL_ARRAY_BUFFER, _selection_vertex_buffer);
+    glBufferData(GL_ARRAY_BUFFER, _selection_vertexes.length() * sizeof(GLfloat),
+            _selection_vertexes.raw(), GL_STREAM_DRAW);
+    glBindBuffer(GL_ARRAY_BUFFER, _selection_tex_coord_buffer);
+    glBufferData(GL_ARRAY_BUFFER, _selection_tex_coords.length() * sizeof(GLfloat),
+            _selection_tex_coords.raw(), GL_STREAM_DRAW);
+
+    draw_text(mvp, color, _selection_vertex_buffer, _selection_tex_coord_buffer,
+            GL_TRIANGLES, 0, unselected_count);
+    draw_text(mvp, selected_color, _selection_vertex_buffer, _selection_tex_coord_buffer,
+            GL_TRIANGLES, unselected_count, vertex_count - unselected_count);
 }
 
 static void copy_freetype_bitmap(FT_Bitmap source, ByteBuffer &dest,
@@ -92,67 +283,81 @@ static void copy_freetype_bitmap(FT_Bitmap source, ByteBuffer &dest,
     }
 }
 
+static int count_lines(const String &text) {
+    int line_count = 1;
+    for (int i = 0; i < text.length(); i += 1) {
+        if (text.at(i) == '\n')
+            line_count += 1;
+    }
+    return line_count;
+}
+
 void Label::update() {
+    if (_auto_fit)
+        fit_font_size();
+
+    FontSize *raster_font_size = (_oversample == 1.0f) ? _font_size :
+        _gui->font_library->get_font_size(lroundf(_font_size->size() * _oversample));
+
+    if (raster_font_size == _rendered_font_size && _gui->font_library->_font_generation == _rendered_font_generation &&
+        _vertical == _rendered_vertical && _kerning == _rendered_kerning &&
+        _oversample == _rendered_oversample &&
+        _gui->text_texture_min_size == _rendered_texture_min_size &&
+        _gui->text_texture_power_of_two == _rendered_texture_power_of_two &&
+        String::compare(layout_text(), _rendered_text) == 0)
+    {
+        return;
+    }
+    _rendered_font_size = raster_font_size;
+    _rendered_font_generation = _gui->font_library->_font_generation;
+    _rendered_vertical = _vertical;
+    _rendered_kerning = _kerning;
+    _rendered_oversample = _oversample;
+    _rendered_texture_min_size = _gui->text_texture_min_size;
+    _rendered_texture_power_of_two = _gui->text_texture_power_of_two;
+    _rendered_text = layout_text();
+
     _letters.clear();
+    _lines.clear();
     if (_text.length() == 0) {
         _width = 0;
         _height = above_size() + below_size();
+        ok_or_panic(_lines.append({ 0, 0, 0, 0, 0, _height, above_size() }));
         return;
     }
 
-    // one pass to determine width and height
-    // pen position represents the baseline. the char can go lower than it
-    float pen_x = 0.0f;
-    int previous_glyph_index = 0;
-    float bounding_width = 0.0f;
-    float prev_right = 0.0f;
-    for (int i = 0; i < _text.length(); i += 1) {
-        uint32_t ch = _text.at(i);
-        FontCacheValue entry = _font_size->font_cache_entry(ch);
-        if (_letters.length() > 0) {
-            FT_Face face = _gui->_default_font_face;
-            FT_Vector kerning;
-            ft_ok(FT_Get_Kerning(face, previous_glyph_index, entry.glyph_index,
-                        FT_KERNING_DEFAULT, &kerning));
-            float kerning_x = ((float)kerning.x) / 64.0f;
-            pen_x += kerning_x;
-        }
-
-        float bmp_start_left = (float)entry.bitmap_glyph->left;
-
-        FT_Bitmap bitmap = entry.bitmap_glyph->bitmap;
-        float bmp_width = bitmap.width;
-        float left = pen_x + bmp_start_left;
-        float right = left + bmp_width;
-        bounding_width = ceilf(right);
-
-        int halfway_left = floorf((prev_right + left) / 2.0f);
-        if (_letters.length() > 0) {
-            Letter *prev_letter = &_letters.at(_letters.length() - 1);
-            prev_letter->full_width = halfway_left - prev_letter->left;
+    // the letters and the texture are in raster pixels, which are
+    // _oversample times smaller than the ones the label is drawn with
+    int tex_width;
+    int tex_height;
+    if (_vertical) {
+        // vertical layouts are not cached, the cache is keyed by text alone
+        label_layout_letters_vertical(raster_font_size, _rendered_text, _letters,
+                &tex_width, &tex_height);
+    } else if (!_kerning) {
+        // the layout cache holds kerned layouts only
+        tex_width = label_layout_letters(raster_font_size, _rendered_text, false, _letters);
+        tex_height = label_layout_lines(raster_font_size, _oversample, _letters, _lines);
+    } else {
+        ByteBuffer encoded_text = _rendered_text.encode();
+        const TextLayout *layout = raster_font_size->get_layout(encoded_text);
+        if (layout) {
+            for (int i = 0; i < layout->letters.length(); i += 1)
+                ok_or_panic(_letters.append(layout->letters.at(i)));
+            tex_width = layout->width;
+        } else {
+            tex_width = label_layout_letters(raster_font_size, _rendered_text, true, _letters);
+            raster_font_size->put_layout(encoded_text, _letters, tex_width);
         }
-
-        ok_or_panic(_letters.append(Letter {
-            ch,
-
-            halfway_left,
-            (int)(left - halfway_left),
-            (int)bmp_width,
-            (int)(right - halfway_left),
-
-            entry.above_size,
-            entry.below_size,
-            entry.bitmap_glyph->top,
-        }));
-
-        previous_glyph_index = entry.glyph_index;
-        prev_right = right;
-        pen_x += ((float)entry.glyph->advance.x) / 65536.0f;
+        tex_height = label_layout_lines(raster_font_size, _oversample, _letters, _lines);
     }
+    _width = ceilf(tex_width / _oversample);
+    _height = ceilf(tex_height / _oversample);
 
-    float bounding_height = above_size() + below_size();
-    _width = bounding_width;
-    _height = bounding_height;
+    // text that is all whitespace has no ink and lays out 0 pixels wide.
+    // draw() skips it, but the texture still needs a valid size.
+    tex_width = max(1, tex_width);
+    tex_height = max(1, tex_height);
 
     glBindBuffer(GL_ARRAY_BUFFER, _vertex_buffer);
     GLfloat vertexes[4][3] = {
@@ -163,43 +368,82 @@ void Label::update() {
     };
     glBufferSubData(GL_ARRAY_BUFFER, 0, 3 * 4 * sizeof(GLfloat), vertexes);
 
-    int img_buf_size =  _width * _height;
+    int alloc_width = max(tex_width, _gui->text_texture_min_size);
+    int alloc_height = max(tex_height, _gui->text_texture_min_size);
+    if (_gui->text_texture_power_of_two) {
+        alloc_width = next_power_of_two(alloc_width);
+        alloc_height = next_power_of_two(alloc_height);
+    }
+    _alloc_width = alloc_width;
+    _alloc_height = alloc_height;
+    float tex_right = tex_width / (float)alloc_width;
+    float tex_bottom = tex_height / (float)alloc_height;
+    glBindBuffer(GL_ARRAY_BUFFER, _tex_coord_buffer);
+    GLfloat tex_coords[4][2] = {
+        {0.0f, 0.0f},
+        {0.0f, tex_bottom},
+        {tex_right, 0.0f},
+        {tex_right, tex_bottom},
+    };
+    glBufferSubData(GL_ARRAY_BUFFER, 0, 2 * 4 * sizeof(GLfloat), tex_coords);
+
+    int img_buf_size = alloc_width * alloc_height;
     _img_buffer.resize(img_buf_size);
     _img_buffer.fill(0);
 
     // second pass to render bitmap
+    int raster_above_size = raster_font_size->_max_above_size;
     for (int i = 0; i < _letters.length(); i += 1) {
         Letter *letter = &_letters.at(i);
-        FontCacheValue entry = _font_size->font_cache_entry(letter->codepoint);
+        if (!_vertical && letter->codepoint == '\n')
+            continue;
+        FontCacheValue entry = raster_font_size->font_cache_entry(letter->codepoint);
         FT_Bitmap bitmap = entry.bitmap_glyph->bitmap;
-        copy_freetype_bitmap(bitmap, _img_buffer,
-                letter->left + letter->bitmap_left, above_size() - letter->bitmap_top, _width);
+        if (_vertical) {
+            copy_freetype_bitmap(bitmap, _img_buffer,
+                    tex_width / 2 + letter->bitmap_left, letter->top + entry.vert_bearing_y, alloc_width);
+        } else {
+            copy_freetype_bitmap(bitmap, _img_buffer, letter->left + letter->bitmap_left,
+                    letter->top + raster_above_size - letter->bitmap_top, alloc_width);
+        }
     }
 
     // send bitmap to GPU
     glActiveTexture(GL_TEXTURE0);
     glBindTexture(GL_TEXTURE_2D, _texture_id);
     glPixelStorei(GL_PACK_ALIGNMENT, 1);
-    glTexImage2D(GL_TEXTURE_2D, 0, GL_RED, bounding_width, bounding_height,
+    glTexImage2D(GL_TEXTURE_2D, 0, GL_RED, alloc_width, alloc_height,
             0, GL_RED, GL_UNSIGNED_BYTE, _img_buffer.raw());
 
     assert_no_gl_error();
 }
 
 int Label::cursor_at_pos(int x, int y) const {
-    if (x < 0)
+    if (_lines.length() == 0)
         return 0;
-    for (int i = 0; i < _letters.length(); i += 1) {
+    int line_index = 0;
+    while (line_index < _lines.length() - 1 &&
+            y >= _lines.at(line_index).top + _lines.at(line_index).height)
+    {
+        line_index += 1;
+    }
+    const LabelLine *line = &_lines.at(line_index);
+    if (x < 0)
+        return line->start;
+    for (int i = line->start; i < line->end; i += 1) {
         const Letter *letter = &_letters.at(i);
+        // clicking never puts the cursor between a base glyph and its mark
+        if (i > line->start && String::is_combining_mark(letter->codepoint))
+            continue;
 
         if (x < letter->left + letter->full_width / 2)
             return i;
     }
-    return _letters.length();
+    return line->end;
 }
 
 void Label::pos_at_cursor(int index, int &x, int &y) const {
-    if (_text.length() == 0) {
+    if (_letters.length() == 0) {
         x = 0;
         y = 0;
         return;
@@ -212,14 +456,27 @@ void Label::pos_at_cursor(int index, int &x, int &y) const {
     }
     if (index >= _letters.length()) {
         const Letter *letter = &_letters.at(_letters.length() - 1);
+        if (letter->codepoint == '\n' && !_vertical) {
+            // after a trailing break, at the start of the empty last line
+            x = 0;
+            y = _lines.last().baseline;
+            return;
+        }
         x = letter->left + letter->full_width;
+        y += _vertical ? 0 : letter->top;
         return;
     }
     const Letter *letter = &_letters.at(index);
     x = letter->left;
+    y += _vertical ? 0 : letter->top;
 }
 
 void Label::get_slice_dimensions(int start, int end, int &start_x, int &end_x) const {
+    if (_letters.length() == 0) {
+        start_x = 0;
+        end_x = 0;
+        return;
+    }
     if (end >= _letters.length()) {
         const Letter *end_letter = &_letters.at(_letters.length() - 1);
         end_x = end_letter->left + end_letter->full_width;
@@ -227,8 +484,12 @@ void Label::get_slice_dimensions(int start, int end, int &start_x, int &end_x) c
         const Letter *end_letter = &_letters.at(end);
         end_x = end_letter->left;
     }
-    const Letter *start_letter = &_letters.at(start);
-    start_x = start_letter->left;
+    if (start >= _letters.length()) {
+        start_x = end_x;
+    } else {
+        const Letter *start_letter = &_letters.at(max(0, start));
+        start_x = start_letter->left;
+    }
 }
 
 void Label::replace_text(int start, int end, String text) {
@@ -236,5 +497,125 @@ void Label::replace_text(int start, int end, String text) {
 }
 
 void Label::set_font_size(int size) {
-    _font_size = _gui->get_font_size(size);
+    _font_size_setting = size;
+    _font_size = _gui->font_library->get_font_size(size);
+    _auto_fit_dirty = true;
+}
+
+void Label::set_auto_fit(int max_width, int max_height, int min_font_size) {
+    _auto_fit = true;
+    _auto_fit_max_width = max_width;
+    _auto_fit_max_height = max_height;
+    _auto_fit_min_font_size = min(min_font_size, _font_size_setting);
+    _auto_fit_dirty = true;
+}
+
+void Label::clear_auto_fit() {
+    _auto_fit = false;
+    _font_size = _gui->font_library->get_font_size(_font_size_setting);
+}
+
+// same as the width computed by the first pass of update()
+TextMetrics Label::measure_at(FontSize *font_size) const {
+    TextMetrics metrics;
+    const String &text = layout_text();
+    ByteBuffer encoded_text;
+    if (_kerning) {
+        // the cache holds kerned measurements only, like the layout cache
+        encoded_text = text.encode();
+        if (font_size->get_metrics(encoded_text, &metrics))
+            return metrics;
+    }
+
+    float pen_x = 0.0f;
+    int previous_glyph_index = 0;
+    float bounding_width = 0.0f;
+    float base_center = 0.0f;
+    bool line_start = true;
+    for (int i = 0; i < text.length(); i += 1) {
+        uint32_t ch = text.at(i);
+        if (ch == '\n') {
+            pen_x = 0.0f;
+            previous_glyph_index = 0;
+            line_start = true;
+            continue;
+        }
+        FontCacheValue entry = font_size->font_cache_entry(ch);
+        if (!line_start && String::is_combining_mark(ch)) {
+            float bmp_width = entry.bitmap_glyph->bitmap.width;
+            bounding_width = max(bounding_width, ceilf(base_center + bmp_width / 2.0f));
+            continue;
+        }
+        if (_kerning && !line_start) {
+            FT_Vector kerning;
+            ft_ok(FT_Get_Kerning(_gui->font_library->_default_font_face, previous_glyph_index, entry.glyph_index,
+                        FT_KERNING_DEFAULT, &kerning));
+            pen_x += ((float)kerning.x) / 64.0f;
+        }
+        float left = pen_x + (float)entry.bitmap_glyph->left;
+        float right = left + entry.bitmap_glyph->bitmap.width;
+        bounding_width = max(bounding_width, ceilf(right));
+        base_center = (left + right) / 2.0f;
+        line_start = false;
+
+        previous_glyph_index = entry.glyph_index;
+        pen_x += ((float)entry.glyph->advance.x) / 65536.0f;
+    }
+    metrics.width = bounding_width;
+    metrics.advance = ceilf(pen_x);
+    metrics.last_use = 0;
+    if (_kerning)
+        font_size->put_metrics(encoded_text, metrics.width, metrics.advance);
+    return metrics;
+}
+
+const String &Label::layout_text() const {
+    if (!_normalize)
+        return _text;
+    if (String::compare(_text, _normalized_source) != 0) {
+        _normalized_source = _text;
+        _normalized_text = _text;
+        _normalized_text.normalize_nfc();
+    }
+    return _normalized_text;
+}
+
+TextMetrics Label::measure() const {
+    if (_font_size != _metrics_font_size || _gui->font_library->_font_generation != _metrics_font_generation ||
+        _kerning != _metrics_kerning || String::compare(layout_text(), _metrics_text) != 0)
+    {
+        _metrics = measure_at(_font_size);
+        _metrics_text = layout_text();
+        _metrics_font_size = _font_size;
+        _metrics_font_generation = _gui->font_library->_font_generation;
+        _metrics_kerning = _kerning;
+    }
+    return _metrics;
+}
+
+bool Label::text_fits(int font_size) {
+    FontSize *candidate = _gui->font_library->get_font_size(font_size);
+    int height = (candidate->_max_above_size + candidate->_max_below_size) * count_lines(layout_text());
+    return height <= _auto_fit_max_height && measure_at(candidate).width <= _auto_fit_max_width;
+}
+
+void Label::fit_font_size() {
+    if (!_auto_fit_dirty && String::compare(_text, _auto_fit_text) == 0)
+        return;
+    _auto_fit_dirty = false;
+    _auto_fit_text = _text;
+
+    // binary search for the largest size that fits. if even the minimum
+    // does not fit, use the minimum and let the text overflow. the set size
+    // may have dropped below the minimum since set_auto_fit
+    int hi = _font_size_setting;
+    int lo = min(_auto_fit_min_font_size, hi);
+    while (lo < hi) {
+        int mid = lo + (hi - lo + 1) / 2;
+        if (text_fits(mid))
+            lo = mid;
+        else
+            hi = mid - 1;
+    }
+    _font_size = _gui->font_library->get_font_size(lo);
 }
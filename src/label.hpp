@@ -5,6 +5,8 @@
 #include "string.hpp"
 #include "glm.hpp"
 #include "font_size.hpp"
+#include "label_layout.hpp"
+#include "label_filter.hpp"
 #include "glfw.hpp"
 
 class Gui;
@@ -28,6 +30,90 @@ public:
     // need to call update() to make it take effect
     void set_font_size(int size);
 
+    // need to call update() to make it take effect. update() will then use
+    // the largest font size, no bigger than the one from set_font_size() and
+    // no smaller than min_font_size, at which the text fits in the box.
+    void set_auto_fit(int max_width, int max_height, int min_font_size);
+    void clear_auto_fit();
+
+    // need to call update() to make it take effect. vertical text is laid
+    // out top to bottom in a single column using the font's vertical metrics,
+    // for CJK. every character, Latin included, is stacked upright rather than
+    // rotated. cursor_at_pos, pos_at_cursor and get_slice_dimensions only
+    // support horizontal text.
+    void set_vertical(bool vertical) {
+        _vertical = vertical;
+        _auto_fit_dirty = true;
+    }
+
+    // need to call update() to make it take effect. glyphs are rasterized
+    // at oversample times the font size and drawn scaled down, so the label
+    // stays sharp when magnified up to that factor. the texture takes
+    // oversample squared times the memory. the cursor and slice queries do
+    // not account for oversample, so leave it at 1 for editable text.
+    void set_oversample(float oversample) {
+        _oversample = oversample;
+    }
+
+    // need to call update() to make it take effect. with kerning off every
+    // glyph advances by its own width only, which keeps columns of digits
+    // aligned and sidesteps fonts with broken kern tables. on by default.
+    void set_kerning(bool kerning) {
+        _kerning = kerning;
+        _auto_fit_dirty = true;
+    }
+
+    // need to call update() to make it take effect. the text is laid out
+    // in Normalization Form C so that precomposed and decomposed accents
+    // look the same, while text() keeps what was set. letter indexes in the
+    // cursor and slice queries count the normalized text, so editable text
+    // turns this off. on by default.
+    void set_normalize(bool normalize) {
+        _normalize = normalize;
+        _auto_fit_dirty = true;
+    }
+
+    // takes effect on the next draw(). how the texture is sampled when the
+    // mvp scales it. see label_sampling(). default LabelFilterLinear
+    void set_filter(LabelFilter filter) {
+        _filter = filter;
+    }
+
+    // takes effect on the next draw(). fills a rounded box of color behind
+    // the text, padding pixels larger than width() by height() on every side,
+    // so that captions stay legible over busy backgrounds.
+    void set_background(const glm::vec4 &color) {
+        _have_background = true;
+        _background_color = color;
+    }
+    void clear_background() {
+        _have_background = false;
+    }
+    void set_background_padding(int padding) {
+        _background_padding = padding;
+    }
+    void set_background_corner_radius(float radius) {
+        _background_corner_radius = radius;
+    }
+    // position of the background box relative to the text, whether or not
+    // a background is set
+    void get_background_rect(int &left, int &top, int &width, int &height) const;
+
+    // takes effect on the next draw(). letters start up to end exclusive,
+    // counted like the cursor queries, get highlight_color behind them and
+    // are drawn in black or white, whichever stands out against it. a
+    // selection that takes in a '\n' is highlighted to the right edge of the
+    // label, so it reads as continuing on the next line. horizontal text only.
+    void set_selection(int start, int end, const glm::vec4 &highlight_color) {
+        _have_selection = true;
+        _selection_start = start;
+        _selection_end = end;
+        _selection_color = highlight_color;
+    }
+    void clear_selection() {
+        _have_selection = false;
+    }
+
     void update();
 
     int width() const {
@@ -38,8 +124,26 @@ public:
         return _height;
     }
 
+    // the width and advance of the text at the current font size, without
+    // rendering it, so update() is not required. cached until the text,
+    // font size, kerning or font change.
+    TextMetrics measure() const;
+
+    // how far text placed after this label should start, including any
+    // trailing whitespace. width() stops at the last inked pixel instead.
+    int advance() const {
+        return measure().advance;
+    }
+
     void draw(const glm::mat4 &mvp, const glm::vec4 &color);
 
+    // each '\n' in the text starts a new line below the one before it.
+    // horizontal labels only, a vertical one has no lines.
+    const List<LabelLine> &line_boxes() const {
+        return _lines;
+    }
+
+    // y picks the line. get_slice_dimensions is for slices within one line
     int cursor_at_pos(int x, int y) const;
     void pos_at_cursor(int index, int &x, int &y) const;
     void get_slice_dimensions(int start, int end, int &start_x, int &end_x) const;
@@ -55,32 +159,94 @@ public:
     void replace_text(int start, int end, String text);
 
 private:
-    struct Letter {
-        uint32_t codepoint;
-
-        int left; // half-way between prev letter and this one. 0 for first letter
-        int bitmap_left; // left + bitmap_left is the first pixel of the letter
-        int bitmap_width; // left + bitmap_left + bitmap_width is the last pixel of the letter
-        int full_width; // left + full_width is half-way between this letter and next
-
-        int above_size;
-        int below_size;
-        int bitmap_top;
-    };
+    typedef TextLayoutLetter Letter;
 
     Gui *_gui;
     int _width;
     int _height;
     GLuint _texture_id;
     GLuint _vertex_buffer;
+    // the part of the texture the text covers, which is all of it unless
+    // Gui::text_texture_power_of_two or text_texture_min_size pad it
+    GLuint _tex_coord_buffer;
 
     String _text;
     FontSize *_font_size;
+    int _font_size_setting;
+    bool _vertical;
+    float _oversample;
+    bool _kerning;
+    bool _normalize;
+    LabelFilter _filter;
+
+    bool _have_background;
+    glm::vec4 _background_color;
+    int _background_padding;
+    float _background_corner_radius;
+
+    bool _have_selection;
+    int _selection_start;
+    int _selection_end;
+    glm::vec4 _selection_color;
+    // triangles covering pieces of the text, rebuilt by every draw() with a
+    // selection. the unselected pieces come first
+    GLuint _selection_vertex_buffer;
+    GLuint _selection_tex_coord_buffer;
+    List<GLfloat> _selection_vertexes;
+    List<GLfloat> _selection_tex_coords;
+    // size of the texture in raster pixels, including any padding
+    int _alloc_width;
+    int _alloc_height;
+
+    bool _auto_fit;
+    int _auto_fit_max_width;
+    int _auto_fit_max_height;
+    int _auto_fit_min_font_size;
+    // the fitted size is only searched for again when these change
+    bool _auto_fit_dirty;
+    String _auto_fit_text;
 
     ByteBuffer _img_buffer;
 
-    // cached from _text on update()
+    // the NFC form of _normalized_source, kept so that update() and
+    // measure() do not normalize again every frame
+    mutable String _normalized_source;
+    mutable String _normalized_text;
+
+    // cached from layout_text() on update()
     List<Letter> _letters;
+    List<LabelLine> _lines;
+    // what the texture currently holds. the font size is the one rasterized
+    // with, which includes _oversample. the oversample is kept as well,
+    // because nearby factors can round to the same font size while scaling
+    // the label's size differently. the Gui texture padding settings are
+    // kept so a change to them reallocates. update() does nothing when these
+    // still match, so callers can update() every frame.
+    String _rendered_text;
+    FontSize *_rendered_font_size;
+    int _rendered_font_generation;
+    bool _rendered_vertical;
+    bool _rendered_kerning;
+    float _rendered_oversample;
+    int _rendered_texture_min_size;
+    bool _rendered_texture_power_of_two;
+
+    // what measure() last returned and what it was measured with
+    mutable TextMetrics _metrics;
+    mutable String _metrics_text;
+    mutable FontSize *_metrics_font_size;
+    mutable int _metrics_font_generation;
+    mutable bool _metrics_kerning;
+
+    const String &layout_text() const;
+    void draw_text(const glm::mat4 &mvp, const glm::vec4 &color, GLuint vertex_buffer,
+            GLuint tex_coord_buffer, GLenum mode, int first, int count);
+    void draw_selection(const glm::mat4 &mvp, const glm::vec4 &color);
+    float letter_edge(const LabelLine *line, int index) const;
+    void add_text_quad(float left, float top, float right, float bottom);
+    TextMetrics measure_at(FontSize *font_size) const;
+    bool text_fits(int font_size);
+    void fit_font_size();
 };
 
 #endif
@@ -0,0 +1,26 @@
+#ifndef LABEL_FILTER_HPP
+#define LABEL_FILTER_HPP
+
+enum LabelFilter {
+    // smooth when the label is drawn scaled
+    LabelFilterLinear,
+    // keeps texels square, for pixel exact text
+    LabelFilterNearest,
+};
+
+// whether a label texture is sampled nearest rather than linear when it is
+// drawn smaller and when it is drawn larger
+struct LabelSampling {
+    bool nearest_min;
+    bool nearest_mag;
+};
+
+// Gui::integer_scale above 1 magnifies every label by a whole number, which
+// stays sharp with nearest. oversampled labels are minified, so integer
+// scaling leaves that alone.
+static inline LabelSampling label_sampling(LabelFilter filter, int integer_scale) {
+    bool nearest = (filter == LabelFilterNearest);
+    return LabelSampling {nearest, nearest || integer_scale > 1};
+}
+
+#endif
@@ -0,0 +1,186 @@
+#include "label_layout.hpp"
+#include "util.hpp"
+
+static void ft_ok(FT_Error err) {
+    if (err)
+        panic("freetype error");
+}
+
+float label_layout_letters(FontSize *font_size, const String &text, bool kerning,
+        List<TextLayoutLetter> &letters)
+{
+    // pen position represents the baseline. the char can go lower than it
+    float pen_x = 0.0f;
+    int previous_glyph_index = 0;
+    float bounding_width = 0.0f;
+    float prev_right = 0.0f;
+    // horizontal center of the last glyph that was not a combining mark
+    float base_center = 0.0f;
+    int line_height = font_size->_max_above_size + font_size->_max_below_size;
+    int line_top = 0;
+    bool line_start = true;
+    for (int i = 0; i < text.length(); i += 1) {
+        uint32_t ch = text.at(i);
+        if (ch == '\n') {
+            // the break has no glyph and sits at the end of the line it ends
+            int break_pos = ceilf(prev_right);
+            if (!line_start) {
+                TextLayoutLetter *prev_letter = &letters.at(letters.length() - 1);
+                prev_letter->full_width = break_pos - prev_letter->left;
+            }
+            ok_or_panic(letters.append(TextLayoutLetter {
+                ch,
+
+                break_pos,
+                0,
+                0,
+                0,
+
+                font_size->_max_above_size,
+                font_size->_max_below_size,
+                0,
+
+                line_top,
+            }));
+
+            pen_x = 0.0f;
+            prev_right = 0.0f;
+            previous_glyph_index = 0;
+            line_top += line_height;
+            line_start = true;
+            continue;
+        }
+        FontCacheValue entry = font_size->font_cache_entry(ch);
+        if (!line_start && String::is_combining_mark(ch)) {
+            // the mark is centered over the base glyph and takes no room of
+            // its own. the pen does not move and kerning is left alone.
+            TextLayoutLetter *prev_letter = &letters.at(letters.length() - 1);
+            int mark_pos = ceilf(prev_right);
+            prev_letter->full_width = mark_pos - prev_letter->left;
+
+            float bmp_width = entry.bitmap_glyph->bitmap.width;
+            float left = base_center - bmp_width / 2.0f;
+            bounding_width = max(bounding_width, ceilf(left + bmp_width));
+
+            ok_or_panic(letters.append(TextLayoutLetter {
+                ch,
+
+                mark_pos,
+                (int)floorf(left - mark_pos),
+                (int)bmp_width,
+                0,
+
+                entry.above_size,
+                entry.below_size,
+                entry.bitmap_glyph->top,
+
+                line_top,
+            }));
+            continue;
+        }
+        if (kerning && !line_start) {
+            FT_Face face = font_size->font_face();
+            FT_Vector kerning_vector;
+            ft_ok(FT_Get_Kerning(face, previous_glyph_index, entry.glyph_index,
+                        FT_KERNING_DEFAULT, &kerning_vector));
+            float kerning_x = ((float)kerning_vector.x) / 64.0f;
+            pen_x += kerning_x;
+        }
+
+        float bmp_start_left = (float)entry.bitmap_glyph->left;
+
+        FT_Bitmap bitmap = entry.bitmap_glyph->bitmap;
+        float bmp_width = bitmap.width;
+        float left = pen_x + bmp_start_left;
+        float right = left + bmp_width;
+        bounding_width = max(bounding_width, ceilf(right));
+
+        int halfway_left = floorf((prev_right + left) / 2.0f);
+        if (!line_start) {
+            TextLayoutLetter *prev_letter = &letters.at(letters.length() - 1);
+            prev_letter->full_width = halfway_left - prev_letter->left;
+        }
+
+        ok_or_panic(letters.append(TextLayoutLetter {
+            ch,
+
+            halfway_left,
+            (int)(left - halfway_left),
+            (int)bmp_width,
+            (int)(right - halfway_left),
+
+            entry.above_size,
+            entry.below_size,
+            entry.bitmap_glyph->top,
+
+            line_top,
+        }));
+
+        line_start = false;
+        previous_glyph_index = entry.glyph_index;
+        prev_right = right;
+        base_center = (left + right) / 2.0f;
+        pen_x += ((float)entry.glyph->advance.x) / 65536.0f;
+    }
+    return bounding_width;
+}
+
+void label_layout_letters_vertical(FontSize *font_size, const String &text,
+        List<TextLayoutLetter> &letters, int *out_width, int *out_height)
+{
+    int pen_y = 0;
+    int half_width = 0;
+    for (int i = 0; i < text.length(); i += 1) {
+        uint32_t ch = text.at(i);
+        FontCacheValue entry = font_size->font_cache_entry(ch);
+        int bmp_width = entry.bitmap_glyph->bitmap.width;
+        half_width = max(half_width, max(-entry.vert_bearing_x, entry.vert_bearing_x + bmp_width));
+
+        ok_or_panic(letters.append(TextLayoutLetter {
+            ch,
+
+            0,
+            entry.vert_bearing_x,
+            bmp_width,
+            bmp_width,
+
+            entry.above_size,
+            entry.below_size,
+            entry.bitmap_glyph->top,
+
+            pen_y,
+        }));
+
+        pen_y += entry.vert_advance;
+    }
+    *out_width = half_width * 2;
+    *out_height = pen_y;
+}
+
+int label_layout_lines(FontSize *font_size, float oversample,
+        const List<TextLayoutLetter> &letters, List<LabelLine> &lines)
+{
+    int line_height = font_size->_max_above_size + font_size->_max_below_size;
+    int line_start = 0;
+    int line_right = 0;
+    for (int i = 0; i <= letters.length(); i += 1) {
+        if (i == letters.length() || letters.at(i).codepoint == '\n') {
+            int line_top = lines.length() * line_height;
+            ok_or_panic(lines.append({
+                line_start,
+                i,
+                0,
+                (int)floorf(line_top / oversample),
+                (int)ceilf(line_right / oversample),
+                (int)ceilf(line_height / oversample),
+                (int)floorf((line_top + font_size->_max_above_size) / oversample),
+            }));
+            line_start = i + 1;
+            line_right = 0;
+            continue;
+        }
+        const TextLayoutLetter *letter = &letters.at(i);
+        line_right = max(line_right, letter->left + letter->bitmap_left + letter->bitmap_width);
+    }
+    return lines.length() * line_height;
+}
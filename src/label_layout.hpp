@@ -0,0 +1,42 @@
+#ifndef LABEL_LAYOUT_HPP
+#define LABEL_LAYOUT_HPP
+
+#include "font_size.hpp"
+#include "string.hpp"
+#include "list.hpp"
+
+// how Label places letters, without the texture, so that it can be checked
+// without a GL context. letters and sizes are in the raster pixels of the
+// font size they are laid out with.
+
+// one line of a label, as laid out by Label::update(), in the pixels the
+// label is drawn with
+struct LabelLine {
+    // the letters on the line, end exclusive. the '\n' that ends the line,
+    // if any, is the letter at end
+    int start;
+    int end;
+    int left;
+    int top;
+    // up to the last inked pixel
+    int width;
+    int height;
+    int baseline;
+};
+
+// fills letters from text and returns the width of the widest line. each
+// '\n' starts a new line below. kerning comes from the font size's face.
+float label_layout_letters(FontSize *font_size, const String &text, bool kerning,
+        List<TextLayoutLetter> &letters);
+
+// fills letters from text top to bottom, centered on one column
+void label_layout_letters_vertical(FontSize *font_size, const String &text,
+        List<TextLayoutLetter> &letters, int *out_width, int *out_height);
+
+// fills lines from horizontal letters, scaled down by oversample to the
+// pixels the label is drawn with, and returns the height of the text in the
+// pixels of font_size
+int label_layout_lines(FontSize *font_size, float oversample,
+        const List<TextLayoutLetter> &letters, List<LabelLine> &lines);
+
+#endif
@@ -0,0 +1,93 @@
+#include "level_meter_widget.hpp"
+#include "gui_window.hpp"
+#include "gui.hpp"
+#include "color.hpp"
+
+static void on_frame_update(Event, void *userdata) {
+    LevelMeterWidget *level_meter_widget = (LevelMeterWidget *)userdata;
+    level_meter_widget->frame_update();
+}
+
+LevelMeterWidget::LevelMeterWidget(GuiWindow *gui_window, AudioGraph *audio_graph) :
+    Widget(gui_window),
+    peak_hold_decay(0.25f),
+    db_scale(true),
+    min_db(-60.0f),
+    audio_graph(audio_graph),
+    bg_color(color_dark_bg()),
+    peak_color(color_meter_peak()),
+    rms_color(color_meter_rms()),
+    hold_color(color_meter_hold()),
+    bar_width(6),
+    bar_spacing(2)
+{
+    channel_count = 0;
+    for (int ch = 0; ch < GENESIS_MAX_CHANNELS; ch += 1)
+        held_peak[ch] = 0.0f;
+    gui->events.attach_handler(EventFrameUpdate, on_frame_update, this);
+}
+
+LevelMeterWidget::~LevelMeterWidget() {
+    gui->events.detach_handler(EventFrameUpdate, on_frame_update);
+}
+
+int LevelMeterWidget::min_width() const {
+    int bar_count = max(channel_count, 2);
+    return bar_spacing + bar_count * (bar_width + bar_spacing);
+}
+
+int LevelMeterWidget::max_width() const {
+    return min_width();
+}
+
+float LevelMeterWidget::level_to_height(float level) const {
+    if (!db_scale)
+        return clamp(0.0f, level, 1.0f);
+    if (level <= 0.0f)
+        return 0.0f;
+    float db = 20.0f * log10f(level);
+    return clamp(0.0f, (db - min_db) / -min_db, 1.0f);
+}
+
+void LevelMeterWidget::frame_update() {
+    // every window's meter reads the same levels, which the audio graph
+    // takes once per frame in audio_graph_flush_events
+    const ChannelLevels *levels = &audio_graph->levels;
+    if (levels->channel_count != channel_count) {
+        channel_count = levels->channel_count;
+        on_size_hints_changed();
+    }
+
+    bool moving = false;
+    for (int ch = 0; ch < channel_count; ch += 1) {
+        float peak_height = level_to_height(levels->peak[ch]);
+        held_peak[ch] = max(peak_height, held_peak[ch] - peak_hold_decay * (float)gui->delta_time);
+        held_peak[ch] = max(held_peak[ch], 0.0f);
+        moving = moving || held_peak[ch] > 0.0f;
+    }
+    if (moving || audio_graph_is_playing(audio_graph))
+        gui->keep_animating();
+}
+
+void LevelMeterWidget::draw(const glm::mat4 &projection) {
+    gui_window->fill_rect(bg_color, left, top, width, height);
+
+    int meter_height = height - bar_spacing * 2;
+    int meter_bottom = top + height - bar_spacing;
+    const ChannelLevels *levels = &audio_graph->levels;
+    int bar_count = min(channel_count, levels->channel_count);
+    for (int ch = 0; ch < bar_count; ch += 1) {
+        int bar_left = left + bar_spacing + ch * (bar_width + bar_spacing);
+
+        int peak_height = level_to_height(levels->peak[ch]) * meter_height;
+        gui_window->fill_rect(peak_color, bar_left, meter_bottom - peak_height, bar_width, peak_height);
+
+        int rms_height = level_to_height(levels->rms[ch]) * meter_height;
+        gui_window->fill_rect(rms_color, bar_left, meter_bottom - rms_height, bar_width, rms_height);
+
+        if (held_peak[ch] > 0.0f) {
+            int hold_top = meter_bottom - (int)(held_peak[ch] * meter_height);
+            gui_window->fill_rect(hold_color, bar_left, hold_top, bar_width, 1);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+#ifndef LEVEL_METER_WIDGET_HPP
+#define LEVEL_METER_WIDGET_HPP
+
+#include "widget.hpp"
+#include "audio_graph.hpp"
+
+// a bar per channel of the mix, from the bottom up, showing the RMS level in
+// front of the peak level. a tick stays at the highest recent peak and falls
+// back slowly, so short peaks are readable.
+class LevelMeterWidget : public Widget {
+public:
+    LevelMeterWidget(GuiWindow *gui_window, AudioGraph *audio_graph);
+    ~LevelMeterWidget() override;
+
+    void draw(const glm::mat4 &projection) override;
+
+    int min_width() const override;
+    int max_width() const override;
+
+    // how fast the held peak tick falls, in meter heights per second.
+    // default 0.25
+    float peak_hold_decay;
+    // bars are scaled in decibels from min_db up to 0 dBFS instead of
+    // linearly. default true and -60
+    bool db_scale;
+    float min_db;
+
+    AudioGraph *audio_graph;
+
+    void frame_update();
+
+private:
+    glm::vec4 bg_color;
+    glm::vec4 peak_color;
+    glm::vec4 rms_color;
+    glm::vec4 hold_color;
+
+    int bar_width;
+    int bar_spacing;
+
+    int channel_count;
+    // in meter heights, 0 to 1
+    float held_peak[GENESIS_MAX_CHANNELS];
+
+    float level_to_height(float level) const;
+};
+
+#endif
@@ -2,13 +2,162 @@
 #include "genesis_editor.hpp"
 #include "error.h"
 
+static int usage(char *exe) {
+    fprintf(stderr, "Usage: %s [options] [audiofile...]\n"
+            "\n"
+            "Audio files are imported into the open project. Use - to read one from\n"
+            "standard input; it is buffered to a temporary file first because\n"
+            "decoding needs to seek. http:// and https:// urls are downloaded to a\n"
+            "temporary file the same way. Downloads finish before the editor\n"
+            "window opens.\n"
+            "\n"
+            "Options:\n"
+            "  --no-session    do not restore the play head or the track editor\n"
+            "                  scroll position from the last session\n"
+            "  --font-size N   font size of the user interface text, default 12\n"
+            "  --scale N       draw the user interface N times larger, default 1\n"
+            "  --high-contrast use stronger colors and larger, bolder text\n"
+            "  --msaa N        anti-alias with N samples per pixel, 0, 2, 4 or 8,\n"
+            "                  default 0. lowered if the driver supports fewer\n"
+            , exe);
+    return 1;
+}
+
+static int read_stdin_to_temp_file(OsTempFile *tmp_file) {
+    ByteBuffer tmp_dir;
+    os_get_temp_dir(tmp_dir);
+
+    int err;
+    if ((err = os_create_temp_file(tmp_dir.raw(), nullptr, tmp_file)))
+        return err;
+
+    char buf[4096];
+    size_t amt;
+    while ((amt = fread(buf, 1, sizeof(buf), stdin)) > 0) {
+        if (fwrite(buf, 1, amt, tmp_file->file) != amt) {
+            fclose(tmp_file->file);
+            os_delete(tmp_file->path.raw());
+            return GenesisErrorFileAccess;
+        }
+    }
+    bool read_error = ferror(stdin);
+    if (fclose(tmp_file->file) || read_error) {
+        os_delete(tmp_file->path.raw());
+        return GenesisErrorFileAccess;
+    }
+    return 0;
+}
+
+static bool is_url(const char *arg) {
+    return strncmp(arg, "http://", 7) == 0 || strncmp(arg, "https://", 8) == 0;
+}
+
+static void print_download_progress(void *userdata, long received, long total) {
+    const char *url = (const char *)userdata;
+    if (total > 0)
+        fprintf(stderr, "\rdownloading %s: %ld%%", url, received * 100 / total);
+    else
+        fprintf(stderr, "\rdownloading %s: %ld KiB", url, received / 1024);
+}
+
+static int download_to_temp_file(const char *url, OsTempFile *tmp_file) {
+    ByteBuffer tmp_dir;
+    os_get_temp_dir(tmp_dir);
+
+    int err;
+    // the extension is a format hint for the decoder
+    if ((err = os_create_temp_file(tmp_dir.raw(), os_url_extension(url).raw(), tmp_file)))
+        return err;
+    fclose(tmp_file->file);
+
+    err = genesis_audio_file_download(url, tmp_file->path.raw(), print_download_progress, (void *)url);
+    fprintf(stderr, "\n");
+    if (err) {
+        os_delete(tmp_file->path.raw());
+        return err;
+    }
+    return 0;
+}
+
 int main(int argc, char *argv[]) {
+    GenesisEditorOptions options;
+    options.restore_session = true;
+    options.font_size = 0;
+    options.scale = 1;
+    options.high_contrast = false;
+    options.msaa_samples = 0;
+
+    bool use_stdin = false;
+    List<const char *> urls;
+    for (int i = 1; i < argc; i += 1) {
+        char *arg = argv[i];
+        if (strcmp(arg, "--no-session") == 0) {
+            options.restore_session = false;
+        } else if (strcmp(arg, "--font-size") == 0) {
+            if (i + 1 >= argc)
+                return usage(argv[0]);
+            options.font_size = atoi(argv[++i]);
+            if (options.font_size <= 0)
+                return usage(argv[0]);
+        } else if (strcmp(arg, "--scale") == 0) {
+            if (i + 1 >= argc)
+                return usage(argv[0]);
+            options.scale = atoi(argv[++i]);
+            if (options.scale < 1)
+                return usage(argv[0]);
+        } else if (strcmp(arg, "--msaa") == 0) {
+            if (i + 1 >= argc)
+                return usage(argv[0]);
+            options.msaa_samples = atoi(argv[++i]);
+            if (options.msaa_samples != 0 && options.msaa_samples != 2 &&
+                options.msaa_samples != 4 && options.msaa_samples != 8)
+            {
+                return usage(argv[0]);
+            }
+        } else if (strcmp(arg, "--high-contrast") == 0) {
+            options.high_contrast = true;
+        } else if (strcmp(arg, "-") == 0 && !use_stdin) {
+            use_stdin = true;
+        } else if (is_url(arg)) {
+            ok_or_panic(urls.append(arg));
+        } else if (arg[0] != '-') {
+            ok_or_panic(options.import_paths.append(arg));
+        } else {
+            return usage(argv[0]);
+        }
+    }
+
     // If genesis depends on libgenesis then we need this code.
     int err;
     if ((err = os_init(nullptr)))
         panic("unable to initialize: %s", genesis_strerror(err));
 
-    GenesisEditor genesis_editor;
+    OsTempFile stdin_file;
+    if (use_stdin) {
+        if ((err = read_stdin_to_temp_file(&stdin_file)))
+            panic("unable to read standard input: %s", genesis_strerror(err));
+        ok_or_panic(options.import_paths.append(stdin_file.path));
+    }
+
+    List<OsTempFile> url_files;
+    for (int i = 0; i < urls.length(); i += 1) {
+        OsTempFile url_file;
+        if ((err = download_to_temp_file(urls.at(i), &url_file))) {
+            fprintf(stderr, "unable to download %s: %s\n", urls.at(i), genesis_strerror(err));
+            continue;
+        }
+        ok_or_panic(url_files.append(url_file));
+        ok_or_panic(options.import_paths.append(url_file.path));
+    }
+
+    GenesisEditor genesis_editor(&options);
+
+    // the project has its own copy now
+    if (use_stdin)
+        os_delete(stdin_file.path.raw());
+    for (int i = 0; i < url_files.length(); i += 1)
+        os_delete(url_files.at(i).path.raw());
+
     genesis_editor.exec();
 
     return 0;
@@ -73,6 +73,14 @@ void MenuWidgetItem::set_caption(const String &caption, int new_mnemonic_index)
     mnemonic_index = new_mnemonic_index;
 }
 
+void MenuWidgetItem::set_shortcut(const KeySequence &new_shortcut) {
+    shortcut = new_shortcut;
+    if (null_key_sequence(shortcut))
+        shortcut_label.set_text("");
+    else
+        shortcut_label.set_text(key_sequence_to_string(shortcut));
+}
+
 VirtKey MenuWidgetItem::get_mnemonic_key() {
     if (mnemonic_index == -1)
         return VirtKeyUnknown;
@@ -146,6 +154,9 @@ ContextMenuWidget::ContextMenuWidget(MenuWidgetItem *menu_widget_item) :
     sub_menu(nullptr),
     parent_menu(nullptr)
 {
+    bg.fill_color = bg_color;
+    bg.border_color = parse_color("#A0A0A0");
+    bg.corner_radius = 3.0f;
     update_model();
 }
 
@@ -209,12 +220,12 @@ void ContextMenuWidget::update_model() {
         }
     }
 
-    bg_model = transform2d(0, 0, calculated_width, calculated_height);
+    bg.update(this, 0, 0, calculated_width, calculated_height);
 }
 
 void ContextMenuWidget::draw(const glm::mat4 &projection) {
     // background
-    gui_window->fill_rect(bg_color, projection * bg_model);
+    bg.draw(gui_window, projection);
 
     for (int i = 0; i < menu_widget_item->children.length(); i += 1) {
         MenuWidgetItem *child = menu_widget_item->children.at(i);
@@ -251,7 +262,7 @@ void ContextMenuWidget::draw(const glm::mat4 &projection) {
     }
 
     if (sub_menu)
-        sub_menu->draw(projection);
+        sub_menu->draw_clipped(projection);
 }
 
 MenuWidgetItem *ContextMenuWidget::get_item_at(int y) {
@@ -5,6 +5,7 @@
 #include "key_event.hpp"
 #include "string.hpp"
 #include "label.hpp"
+#include "rounded_panel.hpp"
 
 struct SpritesheetImage;
 
@@ -30,6 +31,8 @@ public:
     void set_caption(const String &caption);
     void set_caption(const String &caption, int mnemonic_index);
 
+    void set_shortcut(const KeySequence &new_shortcut);
+
     void set_icon(const SpritesheetImage *icon) {
         this->icon = icon;
     }
@@ -100,7 +103,7 @@ public:
     glm::vec4 text_color;
     glm::vec4 text_disabled_color;
     glm::vec4 activated_text_color;
-    glm::mat4 bg_model;
+    RoundedPanel bg;
     void *userdata;
     void (*on_destroy)(ContextMenuWidget *);
     MenuWidgetItem *activated_item;
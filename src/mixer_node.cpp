@@ -7,6 +7,12 @@ struct DescriptorContext {
 struct MixerContext {
     int input_port_count;
     float **read_ptrs;
+
+    // accumulated by the run callback until mixer_node_take_levels
+    atomic_int channel_count;
+    atomic_long level_frame_count;
+    AtomicDouble peak[GENESIS_MAX_CHANNELS];
+    AtomicDouble sum_squares[GENESIS_MAX_CHANNELS];
 };
 
 static void mixer_destroy(struct GenesisNode *node) {
@@ -61,6 +67,8 @@ static void mixer_run(struct GenesisNode *node) {
         min_frame_count = min(min_frame_count, input_frame_count);
     }
 
+    float peak[GENESIS_MAX_CHANNELS] = {0.0f};
+    double sum_squares[GENESIS_MAX_CHANNELS] = {0.0};
     float *out_ptr = genesis_audio_out_port_write_ptr(audio_out_port);
     for (int frame = 0; frame < min_frame_count; frame += 1) {
         float total[GENESIS_MAX_CHANNELS] = {0.0f};
@@ -74,9 +82,18 @@ static void mixer_run(struct GenesisNode *node) {
         for (int ch = 0; ch < channel_count; ch += 1) {
             out_ptr[0] = total[ch];
             out_ptr += 1;
+            peak[ch] = max(peak[ch], fabsf(total[ch]));
+            sum_squares[ch] += total[ch] * total[ch];
         }
     }
 
+    mixer_context->channel_count.store(channel_count);
+    for (int ch = 0; ch < channel_count; ch += 1) {
+        mixer_context->peak[ch].store_max(peak[ch]);
+        mixer_context->sum_squares[ch].add(sum_squares[ch]);
+    }
+    mixer_context->level_frame_count += min_frame_count;
+
     genesis_audio_out_port_advance_write_ptr(audio_out_port, min_frame_count);
     for (int i = 0; i < mixer_context->input_port_count; i += 1) {
         GenesisPort *audio_in_port = genesis_node_port(node, i + 1);
@@ -84,6 +101,17 @@ static void mixer_run(struct GenesisNode *node) {
     }
 }
 
+void mixer_node_take_levels(GenesisNode *node, ChannelLevels *out_levels) {
+    struct MixerContext *mixer_context = (struct MixerContext *)node->userdata;
+    long frame_count = mixer_context->level_frame_count.exchange(0);
+    out_levels->channel_count = mixer_context->channel_count.load();
+    for (int ch = 0; ch < out_levels->channel_count; ch += 1) {
+        out_levels->peak[ch] = mixer_context->peak[ch].exchange(0.0);
+        double sum_squares = mixer_context->sum_squares[ch].exchange(0.0);
+        out_levels->rms[ch] = (frame_count > 0) ? sqrt(sum_squares / frame_count) : 0.0f;
+    }
+}
+
 int create_mixer_descriptor(GenesisPipeline *pipeline, int input_port_count, GenesisNodeDescriptor **out) {
     *out = nullptr;
 
@@ -6,5 +6,16 @@
 int create_mixer_descriptor(GenesisPipeline *pipeline, int input_port_count,
         GenesisNodeDescriptor **out);
 
+// full scale is 1.0
+struct ChannelLevels {
+    int channel_count;
+    float peak[GENESIS_MAX_CHANNELS];
+    float rms[GENESIS_MAX_CHANNELS];
+};
+
+// the levels of what the mixer has put out since the last call, which
+// starts them over. safe to call while the pipeline is running.
+void mixer_node_take_levels(GenesisNode *node, ChannelLevels *out_levels);
+
 #endif
 
@@ -0,0 +1,27 @@
+#ifndef MODAL_INPUT_HPP
+#define MODAL_INPUT_HPP
+
+#include "mouse_event.hpp"
+
+enum ModalMouseTarget {
+    // the event goes to the modal widget
+    ModalMouseTargetModal,
+    // a press outside of the modal dismisses it and goes nowhere else
+    ModalMouseTargetDismiss,
+    // any other event outside of the modal goes nowhere
+    ModalMouseTargetNone,
+};
+
+// where a mouse event goes while a modal is open, given the modal's
+// rectangle in window coordinates. nothing reaches the widgets behind it.
+static inline ModalMouseTarget modal_mouse_target(const MouseEvent *event,
+        int left, int top, int width, int height)
+{
+    if (event->x >= left && event->y >= top && event->x < left + width && event->y < top + height)
+        return ModalMouseTargetModal;
+    if (event->action == MouseActionDown)
+        return ModalMouseTargetDismiss;
+    return ModalMouseTargetNone;
+}
+
+#endif
@@ -0,0 +1,33 @@
+#include "notdef_box.hpp"
+#include "util.hpp"
+
+NotdefBox notdef_box(NotdefStyle style, int font_size) {
+    NotdefBox box = {0, 0, 0, 0, 0, 0};
+    switch (style) {
+        case NotdefStyleFontDefault:
+            panic("the font's own .notdef glyph has no box");
+        case NotdefStyleHidden:
+            return box;
+        case NotdefStyleHollowBox:
+            // sits on the baseline with a pixel of room either side
+            box.advance = max(3, (font_size * 3 + 2) / 5);
+            box.left = 1;
+            box.width = box.advance - 2;
+            box.height = max(3, (font_size * 7 + 5) / 10);
+            box.top = box.height;
+            box.stroke = max(1, font_size / 12);
+            return box;
+    }
+    panic("invalid notdef style");
+}
+
+void notdef_box_pixels(const NotdefBox &box, ByteBuffer &pixels) {
+    pixels.resize(box.width * box.height);
+    for (int y = 0; y < box.height; y += 1) {
+        for (int x = 0; x < box.width; x += 1) {
+            bool edge = x < box.stroke || x >= box.width - box.stroke ||
+                y < box.stroke || y >= box.height - box.stroke;
+            pixels.at(y * box.width + x) = edge ? 255 : 0;
+        }
+    }
+}
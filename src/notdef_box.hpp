@@ -0,0 +1,34 @@
+#ifndef NOTDEF_BOX_HPP
+#define NOTDEF_BOX_HPP
+
+#include "byte_buffer.hpp"
+
+// what to draw for a character the font has no glyph for
+enum NotdefStyle {
+    // the font's own .notdef glyph, often a box, sometimes blank
+    NotdefStyleFontDefault,
+    // a hollow box the width of a typical letter, the same in every font
+    NotdefStyleHollowBox,
+    // nothing, taking no room
+    NotdefStyleHidden,
+};
+
+// the glyph FontSize draws instead of the font's .notdef for a style other
+// than NotdefStyleFontDefault, in pixels. left and top are the bearings from
+// the pen position on the baseline to the top left of the bitmap. all zero
+// for NotdefStyleHidden.
+struct NotdefBox {
+    int advance;
+    int left;
+    int top;
+    int width;
+    int height;
+    int stroke;
+};
+
+NotdefBox notdef_box(NotdefStyle style, int font_size);
+// fills pixels with width by height gray values, row by row, 255 on the
+// box's edges and 0 inside
+void notdef_box_pixels(const NotdefBox &box, ByteBuffer &pixels);
+
+#endif
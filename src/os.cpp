@@ -18,6 +18,7 @@
 #include <time.h>
 #include <assert.h>
 #include <string.h>
+#include <limits.h>
 #include <errno.h>
 
 #if defined(_WIN32)
@@ -149,6 +150,15 @@ void os_get_home_dir(ByteBuffer &out) {
     out = pw->pw_dir;
 }
 
+void os_get_temp_dir(ByteBuffer &out) {
+    const char *env_tmp_dir = getenv("TMPDIR");
+    if (env_tmp_dir && env_tmp_dir[0]) {
+        out = env_tmp_dir;
+        return;
+    }
+    out = "/tmp";
+}
+
 void os_get_app_dir(ByteBuffer &out) {
     ByteBuffer home_dir;
     os_get_home_dir(home_dir);
@@ -181,6 +191,16 @@ void os_get_app_config_path(ByteBuffer &out) {
     os_path_join(out, app_config_dir, "config");
 }
 
+int os_get_exe_dir(ByteBuffer &out) {
+    char buf[PATH_MAX];
+    ssize_t len = readlink("/proc/self/exe", buf, sizeof(buf) - 1);
+    if (len <= 0)
+        return GenesisErrorFileAccess;
+    buf[len] = 0;
+    out = os_path_dirname(buf);
+    return 0;
+}
+
 static int get_random_seed(uint32_t *seed) {
     int fd = open("/dev/urandom", O_RDONLY|O_NONBLOCK);
     if (fd == -1)
@@ -256,9 +276,12 @@ int os_rename_clobber(const char *source, const char *dest) {
     return rename(source, dest) ? GenesisErrorFileAccess : 0;
 }
 
-int os_create_temp_file(const char *dir, OsTempFile *out_tmp_file) {
+int os_create_temp_file(const char *dir, const char *extension, OsTempFile *out_tmp_file) {
     os_path_join(out_tmp_file->path, dir, "XXXXXX");
-    int fd = mkstemp(out_tmp_file->path.raw());
+    int extension_len = extension ? strlen(extension) : 0;
+    if (extension_len > 0)
+        out_tmp_file->path.append(extension);
+    int fd = mkstemps(out_tmp_file->path.raw(), extension_len);
     if (fd == -1)
         return GenesisErrorFileAccess;
     out_tmp_file->file = fdopen(fd, "w+");
@@ -346,6 +369,19 @@ void os_path_remove_extension(ByteBuffer &path) {
     path.resize(dot_index);
 }
 
+ByteBuffer os_url_extension(const char *url) {
+    const char *scheme_end = strstr(url, "://");
+    const char *host = scheme_end ? scheme_end + 3 : url;
+    const char *path = strchr(host, '/');
+    if (!path)
+        return "";
+    ByteBuffer path_buf(path, strcspn(path, "?#"));
+    ByteBuffer extension = os_path_extension(os_path_basename(path_buf));
+    if (extension.length() <= 1)
+        return "";
+    return extension;
+}
+
 int os_readdir(const char *dir, List<OsDirEntry*> &entries) {
     for (int i = 0; i < entries.length(); i += 1)
         os_dir_entry_unref(entries.at(i));
@@ -1201,6 +1237,22 @@ int os_file_size(FILE *file, long *size) {
     return 0;
 }
 
+int os_read_file(const char *path, ByteBuffer &out) {
+    FILE *f = fopen(path, "rb");
+    if (!f)
+        return GenesisErrorFileAccess;
+    long size;
+    int err;
+    if ((err = os_file_size(f, &size))) {
+        fclose(f);
+        return err;
+    }
+    out.resize(size);
+    bool ok = (fread(out.raw(), 1, size, f) == (size_t)size);
+    fclose(f);
+    return ok ? 0 : GenesisErrorFileAccess;
+}
+
 int os_get_current_year(void) {
     time_t t = time(nullptr);
     struct tm *gmt = gmtime(&t);
@@ -21,11 +21,14 @@ struct OsDirEntry {
 int os_init(int (*init_once)(void));
 
 void os_get_home_dir(ByteBuffer &out);
+void os_get_temp_dir(ByteBuffer &out);
 void os_get_app_dir(ByteBuffer &out);
 void os_get_projects_dir(ByteBuffer &out);
 void os_get_app_config_dir(ByteBuffer &out);
 void os_get_app_config_path(ByteBuffer &out);
 void os_get_samples_dir(ByteBuffer &out);
+// directory containing the running executable
+int os_get_exe_dir(ByteBuffer &out);
 
 uint32_t os_random_uint32(void); // 32 bits of entropy
 uint64_t os_random_uint64(void); // 64 bits of entropy
@@ -41,10 +44,14 @@ struct OsTempFile {
     ByteBuffer path;
     FILE *file;
 };
-int os_create_temp_file(const char *dir, OsTempFile *out_tmp_file);
+// extension may be NULL. otherwise it is kept at the end of the file name,
+// including its leading dot
+int os_create_temp_file(const char *dir, const char *extension, OsTempFile *out_tmp_file);
 
 int os_file_flush(FILE *file);
 int os_file_size(FILE *file, long *out_size);
+// reads all of the file at path into out
+int os_read_file(const char *path, ByteBuffer &out);
 
 int os_mkdirp(ByteBuffer path);
 ByteBuffer os_path_dirname(ByteBuffer path);
@@ -52,6 +59,9 @@ ByteBuffer os_path_basename(const ByteBuffer &path);
 void os_path_join(ByteBuffer &out, ByteBuffer left, ByteBuffer right);
 ByteBuffer os_path_extension(ByteBuffer path);
 void os_path_remove_extension(ByteBuffer &path);
+// the extension of the last path component of a url, ignoring the query and
+// fragment, or "" if it has none
+ByteBuffer os_url_extension(const char *url);
 
 // call unref on each entry when done
 int os_copy_no_clobber(const char *source_path, const char *dest_dir,
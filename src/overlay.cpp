@@ -0,0 +1,99 @@
+#include "overlay.hpp"
+#include "gui_window.hpp"
+#include "label.hpp"
+
+#include <math.h>
+
+Overlay::Overlay(GuiWindow *gui_window) :
+    _gui_window(gui_window),
+    _drawn_index(0),
+    _in_frame(false)
+{
+}
+
+Overlay::~Overlay() {
+    for (int i = 0; i < _labels.length(); i += 1)
+        destroy(_labels.at(i), 1);
+}
+
+void Overlay::begin_frame() {
+    assert(!_in_frame);
+    _in_frame = true;
+    pending().clear();
+}
+
+void Overlay::draw_line(int x1, int y1, int x2, int y2, const glm::vec4 &color, int line_width) {
+    assert(_in_frame);
+    float dx = x2 - x1;
+    float dy = y2 - y1;
+    float length = sqrtf(dx * dx + dy * dy);
+    // a rectangle along the line, centered on it
+    glm::mat4 model = glm::translate(glm::mat4(1.0f), glm::vec3(x1, y1, 0.0f));
+    model = glm::rotate(model, atan2f(dy, dx), glm::vec3(0.0f, 0.0f, 1.0f));
+    model = glm::translate(model, glm::vec3(0.0f, -line_width / 2.0f, 0.0f));
+    model = glm::scale(model, glm::vec3(length, line_width, 1.0f));
+
+    ok_or_panic(pending().add_one());
+    Primitive *primitive = &pending().last();
+    primitive->type = PrimitiveTypeRect;
+    primitive->model = model;
+    primitive->color = color;
+}
+
+void Overlay::draw_rect(int left, int top, int width, int height, const glm::vec4 &color) {
+    assert(_in_frame);
+    ok_or_panic(pending().add_one());
+    Primitive *primitive = &pending().last();
+    primitive->type = PrimitiveTypeRect;
+    primitive->model = glm::scale(
+            glm::translate(glm::mat4(1.0f), glm::vec3(left, top, 0.0f)),
+            glm::vec3(width, height, 1.0f));
+    primitive->color = color;
+}
+
+void Overlay::draw_text(int left, int top, const String &text, const glm::vec4 &color) {
+    assert(_in_frame);
+    ok_or_panic(pending().add_one());
+    Primitive *primitive = &pending().last();
+    primitive->type = PrimitiveTypeText;
+    primitive->model = glm::translate(glm::mat4(1.0f), glm::vec3(left, top, 0.0f));
+    primitive->color = color;
+    primitive->text = text;
+}
+
+void Overlay::end_frame() {
+    assert(_in_frame);
+    _in_frame = false;
+
+    List<Primitive> &primitives = pending();
+    int label_count = 0;
+    for (int i = 0; i < primitives.length(); i += 1) {
+        Primitive *primitive = &primitives.at(i);
+        if (primitive->type != PrimitiveTypeText)
+            continue;
+        if (label_count == _labels.length())
+            ok_or_panic(_labels.append(create<Label>(_gui_window->gui)));
+        Label *label = _labels.at(label_count);
+        label->set_text(primitive->text);
+        label->update();
+        primitive->label_index = label_count;
+        label_count += 1;
+    }
+    _drawn_index = 1 - _drawn_index;
+}
+
+void Overlay::draw(const glm::mat4 &projection) {
+    List<Primitive> &primitives = _primitive_lists[_drawn_index];
+    for (int i = 0; i < primitives.length(); i += 1) {
+        Primitive *primitive = &primitives.at(i);
+        glm::mat4 mvp = projection * primitive->model;
+        switch (primitive->type) {
+            case PrimitiveTypeRect:
+                _gui_window->fill_rect(primitive->color, mvp);
+                break;
+            case PrimitiveTypeText:
+                _labels.at(primitive->label_index)->draw(mvp, primitive->color);
+                break;
+        }
+    }
+}
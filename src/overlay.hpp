@@ -0,0 +1,60 @@
+#ifndef OVERLAY_HPP
+#define OVERLAY_HPP
+
+#include "list.hpp"
+#include "glm.hpp"
+#include "string.hpp"
+
+class GuiWindow;
+class Label;
+
+// immediate mode drawing on top of everything else in a window, for guides,
+// measurements and debugging without making a widget. call begin_frame(),
+// then any of the draw functions in window coordinates, then end_frame().
+// the window keeps drawing the last finished frame until the next
+// end_frame(), so this does not need to be called every frame.
+class Overlay {
+public:
+    Overlay(GuiWindow *gui_window);
+    ~Overlay();
+
+    void begin_frame();
+    void draw_line(int x1, int y1, int x2, int y2, const glm::vec4 &color, int line_width);
+    void draw_rect(int left, int top, int width, int height, const glm::vec4 &color);
+    void draw_text(int left, int top, const String &text, const glm::vec4 &color);
+    void end_frame();
+
+    // called by GuiWindow
+    void draw(const glm::mat4 &projection);
+
+private:
+    enum PrimitiveType {
+        PrimitiveTypeRect,
+        PrimitiveTypeText,
+    };
+
+    struct Primitive {
+        PrimitiveType type;
+        // maps the unit square to the rectangle or the line, or places the
+        // text's top left corner
+        glm::mat4 model;
+        glm::vec4 color;
+        String text;
+        // index into _labels, assigned by end_frame()
+        int label_index;
+    };
+
+    GuiWindow *_gui_window;
+    // one list is being built while the other is drawn
+    List<Primitive> _primitive_lists[2];
+    int _drawn_index;
+    bool _in_frame;
+    // reused from frame to frame so that unchanged text is not rendered again
+    List<Label *> _labels;
+
+    List<Primitive> &pending() {
+        return _primitive_lists[1 - _drawn_index];
+    }
+};
+
+#endif
@@ -2311,10 +2311,35 @@ int project_add_audio_asset(Project *project, const ByteBuffer &full_path, Audio
     return 0;
 }
 
-void project_add_audio_clip(Project *project, AudioAsset *audio_asset) {
+// "Artist - Title" from the file's tags, falling back to the file name
+// without its extension when there is no title
+static String audio_clip_name(Project *project, AudioAsset *audio_asset) {
     ByteBuffer name_from_path = audio_asset->path;
     os_path_remove_extension(name_from_path);
-    project_perform_command(create<AddAudioClipCommand>(project, audio_asset, name_from_path));
+    if (project_ensure_audio_asset_loaded(project, audio_asset))
+        return name_from_path;
+
+    const char *title = genesis_audio_file_tag(audio_asset->audio_file, "title");
+    const char *artist = genesis_audio_file_tag(audio_asset->audio_file, "artist");
+    if (!title || !*title)
+        return name_from_path;
+
+    ByteBuffer name_from_tags;
+    if (artist && *artist)
+        name_from_tags.format("%s - %s", artist, title);
+    else
+        name_from_tags = title;
+
+    bool ok;
+    String name(name_from_tags, &ok);
+    if (!ok)
+        return name_from_path;
+    return name;
+}
+
+void project_add_audio_clip(Project *project, AudioAsset *audio_asset) {
+    project_perform_command(create<AddAudioClipCommand>(project, audio_asset,
+                audio_clip_name(project, audio_asset)));
 }
 
 void project_add_audio_clip_segment(Project *project, AudioClip *audio_clip, Track *track,
@@ -2426,6 +2451,7 @@ void project_set_channel_layout(Project *project, const SoundIoChannelLayout *la
 // When you finally get around to genericizing this code, take a peek at
 // whole_notes_per_second at the top of genesis.cpp
 static const double whole_notes_per_second = 140.0 / 60.0;
+static const double zero_crossing_snap_seconds = 0.010;
 
 static double project_whole_notes_to_seconds(Project *project, double whole_notes) {
     return whole_notes / whole_notes_per_second;
@@ -2435,8 +2461,8 @@ static long project_whole_notes_to_frames(Project *project, double whole_notes)
     return project->sample_rate * project_whole_notes_to_seconds(project, whole_notes);
 }
 
-static double project_frames_to_whole_notes(Project *project, long frames) {
-    double seconds = frames / (double)project->sample_rate;
+static double project_frames_to_whole_notes(Project *project, long frames, int sample_rate) {
+    double seconds = frames / (double)sample_rate;
     return whole_notes_per_second * seconds;
 }
 
@@ -2444,11 +2470,16 @@ double project_get_duration_whole_notes(Project *project) {
     double last_pos = 0.0;
     for (int track_i = 0; track_i < project->track_list.length(); track_i += 1) {
         Track *track = project->track_list.at(track_i);
-        AudioClipSegment *last_segment = track->audio_clip_segments.last();
-        long duration_frames = last_segment->end - last_segment->start;
-        double duration_whole_notes = project_frames_to_whole_notes(project, duration_frames);
-        double end_pos = last_segment->pos + duration_whole_notes;
-        last_pos = max(last_pos, end_pos);
+        for (int segment_i = 0; segment_i < track->audio_clip_segments.length(); segment_i += 1) {
+            AudioClipSegment *segment = track->audio_clip_segments.at(segment_i);
+            // segment start and end are frames at the sample rate of the
+            // clip's audio file, which is not necessarily the project's
+            long duration_frames = segment->end - segment->start;
+            int sample_rate = project_audio_clip_sample_rate(project, segment->audio_clip);
+            double end_pos = segment->pos +
+                project_frames_to_whole_notes(project, duration_frames, sample_rate);
+            last_pos = max(last_pos, end_pos);
+        }
     }
     return last_pos;
 }
@@ -2457,6 +2488,100 @@ long project_get_duration_frames(Project *project) {
     return project_whole_notes_to_frames(project, project_get_duration_whole_notes(project));
 }
 
+// the first audio clip segment under whole_notes and the frame of its
+// audio file there, or nullptr where there is no audio
+static AudioClipSegment *segment_at(Project *project, double whole_notes, long *out_frame) {
+    for (int track_i = 0; track_i < project->track_list.length(); track_i += 1) {
+        Track *track = project->track_list.at(track_i);
+        for (int segment_i = 0; segment_i < track->audio_clip_segments.length(); segment_i += 1) {
+            AudioClipSegment *segment = track->audio_clip_segments.at(segment_i);
+            int sample_rate = project_audio_clip_sample_rate(project, segment->audio_clip);
+            double end_pos = segment->pos +
+                project_frames_to_whole_notes(project, segment->end - segment->start, sample_rate);
+            if (whole_notes < segment->pos || whole_notes >= end_pos)
+                continue;
+
+            long offset = project_whole_notes_to_seconds(project, whole_notes - segment->pos) * sample_rate;
+            *out_frame = segment->start + offset;
+            return segment;
+        }
+    }
+    return nullptr;
+}
+
+double project_snap_to_zero_crossing(Project *project, double whole_notes) {
+    long target;
+    AudioClipSegment *segment = segment_at(project, whole_notes, &target);
+    if (!segment)
+        return whole_notes;
+
+    int sample_rate = project_audio_clip_sample_rate(project, segment->audio_clip);
+    long max_distance = zero_crossing_snap_seconds * sample_rate;
+    GenesisAudioFile *audio_file = segment->audio_clip->audio_asset->audio_file;
+    long frame = genesis_audio_file_find_zero_crossing(audio_file, target, max_distance);
+    frame = clamp(segment->start, frame, segment->end);
+    return segment->pos + project_frames_to_whole_notes(project, frame - segment->start, sample_rate);
+}
+
+bool project_stereo_correlation(Project *project, double whole_notes, double window_seconds,
+        double *out_correlation)
+{
+    long end_frame;
+    AudioClipSegment *segment = segment_at(project, whole_notes, &end_frame);
+    if (!segment)
+        return false;
+
+    int sample_rate = project_audio_clip_sample_rate(project, segment->audio_clip);
+    long start_frame = max(segment->start, end_frame - (long)(window_seconds * sample_rate));
+    GenesisAudioFile *audio_file = segment->audio_clip->audio_asset->audio_file;
+    return !genesis_audio_file_stereo_correlation(audio_file, start_frame, end_frame, out_correlation);
+}
+
+void project_format_position(Project *project, double whole_notes, TimeFormat time_format,
+        ByteBuffer &out)
+{
+    static const int beats_per_bar = 4;
+    static const int ticks_per_beat = 960;
+
+    whole_notes = max(0.0, whole_notes);
+    double seconds = project_whole_notes_to_seconds(project, whole_notes);
+    switch (time_format) {
+        case TimeFormatSeconds:
+            out.format("%.3f", seconds);
+            return;
+        case TimeFormatMinSec:
+            {
+                // round to milliseconds first so that 59.9999999 shows as 1:00
+                long total_seconds = lround(seconds * 1000.0) / 1000;
+                out.format("%ld:%02ld", total_seconds / 60, total_seconds % 60);
+                return;
+            }
+        case TimeFormatMinSecMs:
+            {
+                long total_ms = lround(seconds * 1000.0);
+                out.format("%ld:%02ld.%03ld", total_ms / 60000, (total_ms / 1000) % 60, total_ms % 1000);
+                return;
+            }
+        case TimeFormatFrames:
+            out.format("%ld", project_whole_notes_to_frames(project, whole_notes));
+            return;
+        case TimeFormatBarsBeats:
+            {
+                // in 4/4 time a bar is one whole note, so this does not depend on tempo
+                long total_ticks = lround(whole_notes * beats_per_bar * ticks_per_beat);
+                long ticks_per_bar = beats_per_bar * ticks_per_beat;
+                long bar = total_ticks / ticks_per_bar;
+                long beat = (total_ticks % ticks_per_bar) / ticks_per_beat;
+                long tick = total_ticks % ticks_per_beat;
+                out.format("%ld.%ld.%03ld", bar + 1, beat + 1, tick);
+                return;
+            }
+        case TimeFormatCount:
+            break;
+    }
+    panic("invalid time format");
+}
+
 AddTrackCommand::AddTrackCommand(Project *project, String name, const SortKey &sort_key) :
     Command(project),
     name(name),
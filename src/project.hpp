@@ -112,6 +112,18 @@ struct Effect {
     MixerLine *mixer_line;
 };
 
+// how a position in the project is shown to the user
+enum TimeFormat {
+    TimeFormatSeconds,
+    TimeFormatMinSec,
+    TimeFormatMinSecMs,
+    TimeFormatFrames,
+    // bar.beat.tick, assuming 4/4 time
+    TimeFormatBarsBeats,
+
+    TimeFormatCount,
+};
+
 struct PlayChannelContext {
     struct GenesisAudioFileIterator iter;
     long offset;
@@ -461,4 +473,19 @@ void project_set_channel_layout(Project *project, const SoundIoChannelLayout *la
 double project_get_duration_whole_notes(Project *project);
 long project_get_duration_frames(Project *project);
 
+// moves whole_notes to the nearest zero crossing of the first audio clip
+// segment under it, looking up to 10 ms either way.
+// returns whole_notes unchanged where there is no audio.
+double project_snap_to_zero_crossing(Project *project, double whole_notes);
+// the stereo correlation of the first audio clip segment under whole_notes,
+// over the window_seconds of it that lead up there. see
+// genesis_audio_file_stereo_correlation. returns false where there is no
+// audio or it is not stereo.
+bool project_stereo_correlation(Project *project, double whole_notes, double window_seconds,
+        double *out_correlation);
+
+// formats a position in whole notes. frames are at the project sample rate
+void project_format_position(Project *project, double whole_notes, TimeFormat time_format,
+        ByteBuffer &out);
+
 #endif
@@ -119,7 +119,7 @@ TextWidget *ProjectPropsWidget::create_form_label(const char *text) {
 
 
 void ProjectPropsWidget::draw(const glm::mat4 &projection) {
-    layout.draw(projection);
+    layout.draw_clipped(projection);
 }
 
 void ProjectPropsWidget::on_resize() {
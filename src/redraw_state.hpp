@@ -0,0 +1,36 @@
+#ifndef REDRAW_STATE_HPP
+#define REDRAW_STATE_HPP
+
+// tracks whether anything on screen is animating. call keep_animating()
+// during a main loop iteration while something changes every frame, and
+// end_iteration() once per iteration after all the handlers ran.
+class RedrawState {
+public:
+    RedrawState() :
+        _keep_animating(false),
+        _continuous_redraw(false)
+    {
+    }
+
+    void keep_animating() {
+        _keep_animating = true;
+    }
+
+    // starts the next iteration. returns whether anything called
+    // keep_animating() in the one that ended
+    bool end_iteration() {
+        _continuous_redraw = _keep_animating;
+        _keep_animating = false;
+        return _continuous_redraw;
+    }
+
+    bool needs_continuous_redraw() const {
+        return _continuous_redraw;
+    }
+
+private:
+    bool _keep_animating;
+    bool _continuous_redraw;
+};
+
+#endif
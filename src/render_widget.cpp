@@ -135,7 +135,7 @@ TextWidget *RenderWidget::create_form_label(const char *text) {
 
 
 void RenderWidget::draw(const glm::mat4 &projection) {
-    main_layout.draw(projection);
+    main_layout.draw_clipped(projection);
 }
 
 void RenderWidget::on_resize() {
@@ -145,7 +145,7 @@ void ResourcesTreeWidget::clear_display_nodes() {
 
 void ResourcesTreeWidget::draw(const glm::mat4 &projection) {
     bg.draw(gui_window, projection);
-    scroll_bar->draw(projection);
+    scroll_bar->draw_clipped(projection);
 
 
     glEnable(GL_STENCIL_TEST);
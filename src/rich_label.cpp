@@ -0,0 +1,71 @@
+#include "rich_label.hpp"
+
+RichLabel::RichLabel(Gui *gui) :
+    _gui(gui),
+    _span_count(0),
+    _line_break_pending(false),
+    _width(0),
+    _height(0),
+    _above_size(0)
+{
+}
+
+RichLabel::~RichLabel() {
+    for (int i = 0; i < _spans.length(); i += 1)
+        destroy(_spans.at(i).label, 1);
+}
+
+void RichLabel::clear() {
+    _span_count = 0;
+    _line_break_pending = false;
+}
+
+void RichLabel::add_span(const String &text, int font_size, const glm::vec4 &color) {
+    // labels are kept across clear() so that setting the same spans again
+    // hits their render caches instead of rasterizing anew
+    if (_span_count == _spans.length())
+        ok_or_panic(_spans.append(Span {create<Label>(_gui), color, glm::mat4(1.0f), false}));
+    Span *span = &_spans.at(_span_count);
+    _span_count += 1;
+    span->label->set_font_size(font_size);
+    span->label->set_text(text);
+    span->color = color;
+    span->line_break_before = _line_break_pending;
+    _line_break_pending = false;
+}
+
+void RichLabel::add_line_break() {
+    _line_break_pending = true;
+}
+
+void RichLabel::update() {
+    // whitespace at the end of a span still pushes the next one along, so
+    // spans advance by the pen position. lines end at the last inked pixel.
+    _span_metrics.clear();
+    for (int i = 0; i < _span_count; i += 1) {
+        Span *span = &_spans.at(i);
+        Label *label = span->label;
+        label->update();
+        ok_or_panic(_span_metrics.append(RichTextSpanMetrics {
+            label->advance(),
+            label->width(),
+            label->above_size(),
+            label->below_size(),
+            span->line_break_before,
+        }));
+    }
+    rich_text_layout(_span_metrics, _span_positions, _lines, &_width, &_height);
+    _above_size = (_lines.length() > 0) ? _lines.at(0).above_size : 0;
+
+    for (int i = 0; i < _span_count; i += 1) {
+        RichTextSpanPos *pos = &_span_positions.at(i);
+        _spans.at(i).model = glm::translate(glm::mat4(1.0f), glm::vec3(pos->left, pos->top, 0.0f));
+    }
+}
+
+void RichLabel::draw(const glm::mat4 &mvp) {
+    for (int i = 0; i < _span_count; i += 1) {
+        Span *span = &_spans.at(i);
+        span->label->draw(mvp * span->model, span->color);
+    }
+}
@@ -0,0 +1,65 @@
+#ifndef RICH_LABEL_HPP
+#define RICH_LABEL_HPP
+
+#include "label.hpp"
+#include "list.hpp"
+#include "rich_text_layout.hpp"
+
+// text made of spans that each have their own font size and color, such as
+// a bold-looking name among smaller text. spans are laid out left to right
+// on their line's baseline, and each line is as tall as its largest ascent
+// plus its largest descent. each span is its own Label, so glyphs and
+// layouts come from the same per font size caches as plain text. use Label
+// when the text has a single size and color.
+class RichLabel {
+public:
+    RichLabel(Gui *gui);
+    ~RichLabel();
+
+    RichLabel(const RichLabel &copy) = delete;
+    RichLabel &operator=(const RichLabel &copy) = delete;
+
+    // need to call update() to make these take effect
+    void clear();
+    void add_span(const String &text, int font_size, const glm::vec4 &color);
+    // the next span starts a new line
+    void add_line_break();
+
+    void update();
+    void draw(const glm::mat4 &mvp);
+
+    int width() const {
+        return _width;
+    }
+
+    int height() const {
+        return _height;
+    }
+
+    // distance from the top to the baseline of the first line
+    int above_size() const {
+        return _above_size;
+    }
+
+private:
+    struct Span {
+        Label *label;
+        glm::vec4 color;
+        glm::mat4 model;
+        bool line_break_before;
+    };
+
+    Gui *_gui;
+    // only the first _span_count are in use
+    List<Span> _spans;
+    int _span_count;
+    bool _line_break_pending;
+    List<RichTextSpanMetrics> _span_metrics;
+    List<RichTextSpanPos> _span_positions;
+    List<RichTextLine> _lines;
+    int _width;
+    int _height;
+    int _above_size;
+};
+
+#endif
@@ -0,0 +1,78 @@
+#ifndef RICH_TEXT_LAYOUT_HPP
+#define RICH_TEXT_LAYOUT_HPP
+
+#include "list.hpp"
+#include "util.hpp"
+
+struct RichTextSpanMetrics {
+    // pen movement to where the next span on the same line starts
+    int advance;
+    // up to the last inked pixel
+    int width;
+    int above_size;
+    int below_size;
+    // the span starts a new line under the previous one
+    bool line_break_before;
+};
+
+struct RichTextSpanPos {
+    int left;
+    int top;
+};
+
+struct RichTextLine {
+    int top;
+    // distance from the top of the line to its baseline
+    int above_size;
+    int below_size;
+};
+
+// lays spans out left to right, starting a new line wherever a span asks for
+// one. each line is as tall as its largest ascent plus its largest descent,
+// and every span on it sits on the line's baseline. lines stack with no gap.
+// the width is that of the widest line, up to the last inked pixel.
+static inline void rich_text_layout(List<RichTextSpanMetrics> &spans,
+        List<RichTextSpanPos> &out_positions, List<RichTextLine> &out_lines,
+        int *out_width, int *out_height)
+{
+    out_positions.clear();
+    out_lines.clear();
+    *out_width = 0;
+    *out_height = 0;
+    if (spans.length() == 0)
+        return;
+
+    for (int i = 0; i < spans.length(); i += 1) {
+        RichTextSpanMetrics *span = &spans.at(i);
+        if (i == 0 || span->line_break_before)
+            ok_or_panic(out_lines.append(RichTextLine {0, 0, 0}));
+        RichTextLine *line = &out_lines.last();
+        line->above_size = max(line->above_size, span->above_size);
+        line->below_size = max(line->below_size, span->below_size);
+    }
+
+    int top = 0;
+    for (int i = 0; i < out_lines.length(); i += 1) {
+        RichTextLine *line = &out_lines.at(i);
+        line->top = top;
+        top += line->above_size + line->below_size;
+    }
+    *out_height = top;
+
+    int line_index = -1;
+    int x = 0;
+    for (int i = 0; i < spans.length(); i += 1) {
+        RichTextSpanMetrics *span = &spans.at(i);
+        if (i == 0 || span->line_break_before) {
+            line_index += 1;
+            x = 0;
+        }
+        RichTextLine *line = &out_lines.at(line_index);
+        int span_top = line->top + line->above_size - span->above_size;
+        ok_or_panic(out_positions.append(RichTextSpanPos {x, span_top}));
+        *out_width = max(*out_width, x + span->width);
+        x += span->advance;
+    }
+}
+
+#endif
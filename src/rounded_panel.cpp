@@ -0,0 +1,24 @@
+#include "rounded_panel.hpp"
+#include "color.hpp"
+#include "gui_window.hpp"
+
+RoundedPanel::RoundedPanel() :
+    corner_radius(4.0f),
+    border_width(1.0f),
+    fill_color(color_dark_bg()),
+    border_color(color_dark_border()),
+    width(0),
+    height(0)
+{
+}
+
+void RoundedPanel::update(Widget *widget, int left, int top, int width, int height) {
+    this->width = width;
+    this->height = height;
+    model = widget->transform2d(left, top, width, height);
+}
+
+void RoundedPanel::draw(GuiWindow *gui_window, const glm::mat4 &projection) {
+    gui_window->fill_rounded_rect(fill_color, border_color, corner_radius, border_width,
+            width, height, projection * model);
+}
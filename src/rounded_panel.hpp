@@ -0,0 +1,26 @@
+#ifndef ROUNDED_PANEL
+#define ROUNDED_PANEL
+
+#include "widget.hpp"
+
+// background for menus and popups. the corners are drawn with a signed
+// distance function so that they stay smooth at any radius and scale
+class RoundedPanel {
+public:
+    RoundedPanel();
+    ~RoundedPanel() {}
+
+    void update(Widget *widget, int left, int top, int width, int height);
+    void draw(GuiWindow *gui_window, const glm::mat4 &projection);
+
+    float corner_radius;
+    float border_width;
+    glm::vec4 fill_color;
+    glm::vec4 border_color;
+
+    glm::mat4 model;
+    int width;
+    int height;
+};
+
+#endif
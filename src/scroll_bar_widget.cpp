@@ -77,6 +77,13 @@ void ScrollBarWidget::on_mouse_wheel(const MouseWheelEvent *event) {
 }
 
 void ScrollBarWidget::on_mouse_move(const MouseEvent *event) {
+    bool over_handle = (event->x >= handle_left && event->x < handle_right &&
+                        event->y >= handle_top && event->y < handle_bottom);
+    if (over_handle || dragging_handle)
+        gui_window->set_cursor_hand();
+    else
+        gui_window->set_cursor_default();
+
     switch (event->action) {
         case MouseActionDown:
             {
@@ -85,9 +92,7 @@ void ScrollBarWidget::on_mouse_move(const MouseEvent *event) {
                 drag_start_x = event->x;
                 drag_start_y = event->y;
                 dragging_handle = true;
-                bool clicked_handle = (event->x >= handle_left && event->x < handle_right &&
-                                       event->y >= handle_top && event->y < handle_bottom);
-                if (!clicked_handle) {
+                if (!over_handle) {
                     float amt = get_pos_amt(event->x, event->y);
                     set_value(min_value + amt * (max_value - min_value));
                     events.trigger(EventScrollValueChange);
@@ -28,6 +28,7 @@ public:
     void on_mouse_move(const MouseEvent *event) override;
     void on_mouse_out(const MouseEvent *event) override;
     void on_mouse_over(const MouseEvent *event) override;
+    bool focusable() const override { return true; }
     void on_resize() override { update_model(); }
 
     int min_width() const override;
@@ -66,8 +66,8 @@ void SequencerWidget::draw(const glm::mat4 &projection) {
     bg.draw(gui_window, projection);
     grid_bg.draw(gui_window, projection);
 
-    horiz_scroll_bar->draw(projection);
-    vert_scroll_bar->draw(projection);
+    horiz_scroll_bar->draw_clipped(projection);
+    vert_scroll_bar->draw_clipped(projection);
 
     for (int i = 0; i < grid_rows.length(); i += 1) {
         SequencerWidgetGridRow *grid_row = &grid_rows.at(i);
@@ -96,6 +96,12 @@ static int on_string(struct LaxJsonContext *json,
             {
                 if (ByteBuffer::compare(value, "open_project_id") == 0) {
                     sf->state = SettingsFileStateOpenProjectFile;
+                } else if (ByteBuffer::compare(value, "open_project_play_head") == 0) {
+                    sf->state = SettingsFileStateOpenProjectPlayHead;
+                } else if (ByteBuffer::compare(value, "open_project_scroll_pos") == 0) {
+                    sf->state = SettingsFileStateOpenProjectScrollPos;
+                } else if (ByteBuffer::compare(value, "open_project_markers") == 0) {
+                    sf->state = SettingsFileStateOpenProjectMarkers;
                 } else if (ByteBuffer::compare(value, "user_name") == 0) {
                     sf->state = SettingsFileStateUserName;
                 } else if (ByteBuffer::compare(value, "user_id") == 0) {
@@ -108,12 +114,16 @@ static int on_string(struct LaxJsonContext *json,
                     sf->state = SettingsFileStateExpectSampleDirs;
                 } else if (ByteBuffer::compare(value, "latency") == 0) {
                     sf->state = SettingsFileStateLatency;
+                } else if (ByteBuffer::compare(value, "snap_to_zero_crossing") == 0) {
+                    sf->state = SettingsFileStateSnapToZeroCrossing;
                 } else if (ByteBuffer::compare(value, "device_designations") == 0) {
                     sf->state = SettingsFileStateDeviceDesignations;
                 } else if (ByteBuffer::compare(value, "default_render_format") == 0) {
                     sf->state = SettingsFileStateDefaultRenderFormat;
                 } else if (ByteBuffer::compare(value, "default_render_params") == 0) {
                     sf->state = SettingsFileStateDefaultRenderParams;
+                } else if (ByteBuffer::compare(value, "key_bindings") == 0) {
+                    sf->state = SettingsFileStateKeyBindings;
                 } else {
                     return parse_error(sf, "invalid setting name");
                 }
@@ -194,6 +204,33 @@ static int on_string(struct LaxJsonContext *json,
                 ok_or_panic(sf->current_dock->tabs.append(title));
                 break;
             }
+        case SettingsFileStateKeyBindingProp:
+            ok_or_panic(sf->key_bindings.add_one());
+            sf->key_bindings.last().action = value;
+            sf->state = SettingsFileStateKeyBindingValue;
+            break;
+        case SettingsFileStateKeyBindingValue:
+            sf->key_bindings.last().key_sequence = value;
+            sf->state = SettingsFileStateKeyBindingProp;
+            break;
+        case SettingsFileStateOpenProjectMarkerItemProp:
+            if (ByteBuffer::compare(value, "pos") == 0) {
+                sf->state = SettingsFileStateOpenProjectMarkerPos;
+            } else if (ByteBuffer::compare(value, "name") == 0) {
+                sf->state = SettingsFileStateOpenProjectMarkerName;
+            } else {
+                return parse_error(sf, "invalid marker property name");
+            }
+            break;
+        case SettingsFileStateOpenProjectMarkerName:
+            {
+                bool ok;
+                sf->current_marker->name = String(value, &ok);
+                if (!ok)
+                    return parse_error(sf, "invalid UTF-8");
+                sf->state = SettingsFileStateOpenProjectMarkerItemProp;
+                break;
+            }
         case SettingsFileStateOpenWindowItemProp:
             if (ByteBuffer::compare(value, "perspective") == 0) {
                 sf->state = SettingsFileStateOpenWindowPerspectiveIndex;
@@ -318,6 +355,18 @@ static int on_number(struct LaxJsonContext *json, double x) {
             sf->latency = x;
             sf->state = SettingsFileStateReadyForProp;
             break;
+        case SettingsFileStateOpenProjectPlayHead:
+            sf->open_project_play_head = x;
+            sf->state = SettingsFileStateReadyForProp;
+            break;
+        case SettingsFileStateOpenProjectScrollPos:
+            sf->open_project_scroll_pos = x;
+            sf->state = SettingsFileStateReadyForProp;
+            break;
+        case SettingsFileStateOpenProjectMarkerPos:
+            sf->current_marker->pos = x;
+            sf->state = SettingsFileStateOpenProjectMarkerItemProp;
+            break;
         case SettingsFileStateDefaultRenderParamsBitRate:
             sf->default_render_bit_rates[sf->current_default_render_params_format] = (int)x;
             sf->state = SettingsFileStateDefaultRenderParamsProp;
@@ -352,6 +401,12 @@ static int on_primitive(struct LaxJsonContext *json, enum LaxJsonType type) {
             sf->current_sf_device_id->is_raw = (type == LaxJsonTypeTrue);
             sf->state = SettingsFileStateDeviceDesignationIdProp;
             break;
+        case SettingsFileStateSnapToZeroCrossing:
+            if (type != LaxJsonTypeTrue && type != LaxJsonTypeFalse)
+                return parse_error(sf, "expected boolean");
+            sf->snap_to_zero_crossing = (type == LaxJsonTypeTrue);
+            sf->state = SettingsFileStateReadyForProp;
+            break;
         default:
             return parse_error(sf, "unexpected primitive");
     }
@@ -412,6 +467,20 @@ static int on_begin(struct LaxJsonContext *json, enum LaxJsonType type) {
                 return parse_error(sf, "expected array");
             sf->state = SettingsFileStateOpenWindowItem;
             break;
+        case SettingsFileStateOpenProjectMarkers:
+            if (type != LaxJsonTypeArray)
+                return parse_error(sf, "expected array");
+            sf->state = SettingsFileStateOpenProjectMarkerItem;
+            break;
+        case SettingsFileStateOpenProjectMarkerItem:
+            if (type != LaxJsonTypeObject)
+                return parse_error(sf, "expected object");
+            ok_or_panic(sf->open_project_markers.add_one());
+            sf->current_marker = &sf->open_project_markers.last();
+            sf->current_marker->pos = 0.0;
+            sf->current_marker->name = "";
+            sf->state = SettingsFileStateOpenProjectMarkerItemProp;
+            break;
         case SettingsFileStateOpenWindowItem:
             if (type != LaxJsonTypeObject)
                 return parse_error(sf, "expected object");
@@ -424,6 +493,11 @@ static int on_begin(struct LaxJsonContext *json, enum LaxJsonType type) {
                 return parse_error(sf, "expected object");
             sf->state = SettingsFileStateDeviceDesignationProp;
             break;
+        case SettingsFileStateKeyBindings:
+            if (type != LaxJsonTypeObject)
+                return parse_error(sf, "expected object");
+            sf->state = SettingsFileStateKeyBindingProp;
+            break;
         case SettingsFileStateDeviceDesignationValue:
             if (type != LaxJsonTypeObject)
                 return parse_error(sf, "expected null or object");
@@ -490,6 +564,22 @@ static int on_end(struct LaxJsonContext *json, enum LaxJsonType type) {
                 return parse_error(sf, "expected array");
             sf->state = SettingsFileStateReadyForProp;
             break;
+        case SettingsFileStateOpenProjectMarkerItem:
+            if (type != LaxJsonTypeArray)
+                return parse_error(sf, "expected array");
+            sf->current_marker = nullptr;
+            sf->state = SettingsFileStateReadyForProp;
+            break;
+        case SettingsFileStateKeyBindingProp:
+            if (type != LaxJsonTypeObject)
+                return parse_error(sf, "expected end object");
+            sf->state = SettingsFileStateReadyForProp;
+            break;
+        case SettingsFileStateOpenProjectMarkerItemProp:
+            if (type != LaxJsonTypeObject)
+                return parse_error(sf, "expected end object");
+            sf->state = SettingsFileStateOpenProjectMarkerItem;
+            break;
         case SettingsFileStateOpenWindowItemProp:
             if (type != LaxJsonTypeObject)
                 return parse_error(sf, "expected end object");
@@ -695,6 +785,27 @@ static void json_line_open_windows(FILE *f, int indent, const char *key,
     json_line_outdent(f, &indent, "],");
 }
 
+static void json_line_markers(FILE *f, int indent, const char *key,
+        const List<SettingsFileMarker> &markers)
+{
+    do_indent(f, indent);
+    fprintf(f, "%s: ", key);
+    json_line_indent(f, &indent, "[");
+
+    for (int i = 0; i < markers.length(); i += 1) {
+        const SettingsFileMarker *marker = &markers.at(i);
+
+        do_indent(f, indent);
+        json_line_indent(f, &indent, "{");
+
+        json_line_double(f, indent, "pos", marker->pos);
+        json_line_str(f, indent, "name", marker->name.encode());
+
+        json_line_outdent(f, &indent, "},");
+    }
+    json_line_outdent(f, &indent, "],");
+}
+
 static void json_line_perspectives(FILE *f, int indent, const char *key,
         const List<SettingsFilePerspective> &perspectives)
 {
@@ -744,6 +855,21 @@ static void json_line_device_designations(FILE *f, int indent, const char *key,
     json_line_outdent(f, &indent, "},");
 }
 
+static void json_line_key_bindings(FILE *f, int indent, const char *key,
+        const List<SettingsFileKeyBinding> &key_bindings)
+{
+    do_indent(f, indent);
+    fprintf(f, "%s: ", key);
+    json_line_indent(f, &indent, "{");
+
+    for (int i = 0; i < key_bindings.length(); i += 1) {
+        const SettingsFileKeyBinding *key_binding = &key_bindings.at(i);
+        json_line_str(f, indent, key_binding->action.raw(), key_binding->key_sequence);
+    }
+
+    json_line_outdent(f, &indent, "},");
+}
+
 static void json_line_render_format_defaults(FILE *f, int indent, const char *key,
         const SoundIoFormat *sample_formats, const int *bit_rates)
 {
@@ -781,6 +907,8 @@ SettingsFile *settings_file_open(const ByteBuffer &path) {
 
     // default settings
     sf->open_project_id = uint256::zero();
+    sf->open_project_play_head = 0.0;
+    sf->open_project_scroll_pos = 0.0;
     sf->user_name = "";
     sf->user_id = uint256::zero();
     sf->default_render_format = RenderFormatTypeInvalid;
@@ -875,7 +1003,7 @@ void settings_file_clear_dock(SettingsFileDock *dock) {
 
 int settings_file_commit(SettingsFile *sf) {
     OsTempFile tmp_file;
-    int err = os_create_temp_file(os_path_dirname(sf->path).raw(), &tmp_file);
+    int err = os_create_temp_file(os_path_dirname(sf->path).raw(), nullptr, &tmp_file);
     if (err)
         return err;
 
@@ -906,6 +1034,18 @@ int settings_file_commit(SettingsFile *sf) {
     json_line_uint256(f, indent, "open_project_id", sf->open_project_id);
     fprintf(f, "\n");
 
+    json_line_comment(f, indent, "where the play head was when the open project was last closed");
+    json_line_double(f, indent, "open_project_play_head", sf->open_project_play_head);
+    fprintf(f, "\n");
+
+    json_line_comment(f, indent, "left edge of the track editor when the open project was last closed");
+    json_line_double(f, indent, "open_project_scroll_pos", sf->open_project_scroll_pos);
+    fprintf(f, "\n");
+
+    json_line_comment(f, indent, "markers in the open project, in whole notes");
+    json_line_markers(f, indent, "open_project_markers", sf->open_project_markers);
+    fprintf(f, "\n");
+
     json_line_comment(f, indent, "these perspectives are available for you to choose from");
     json_line_perspectives(f, indent, "perspectives", sf->perspectives);
     fprintf(f, "\n");
@@ -920,6 +1060,11 @@ int settings_file_commit(SettingsFile *sf) {
     json_line_double(f, indent, "latency", sf->latency);
     fprintf(f, "\n");
 
+    json_line_comment(f, indent, "in the track editor, move the play head and selection edges");
+    json_line_comment(f, indent, "to the nearest zero crossing, so that edits do not click");
+    json_line_bool(f, indent, "snap_to_zero_crossing", sf->snap_to_zero_crossing);
+    fprintf(f, "\n");
+
     json_line_comment(f, indent, "which actual devices correspond to virtual devices");
     json_line_comment(f, indent, "null means use the system default device for this virtual device");
     json_line_device_designations(f, indent, "device_designations", sf->device_designations);
@@ -935,6 +1080,11 @@ int settings_file_commit(SettingsFile *sf) {
             sf->default_render_sample_formats, sf->default_render_bit_rates);
     fprintf(f, "\n");
 
+    json_line_comment(f, indent, "key sequence for each editor command, such as \"Ctrl+Shift+Z\"");
+    json_line_comment(f, indent, "an empty string leaves the command without a shortcut");
+    json_line_key_bindings(f, indent, "key_bindings", sf->key_bindings);
+    fprintf(f, "\n");
+
     json_line_outdent(f, &indent, "}");
 
     if (fclose(f))
@@ -965,3 +1115,20 @@ void settings_file_set_default_render_bit_rate(SettingsFile *sf,
     sf->default_render_bit_rates[format_type] = bit_rate;
     sf->events.trigger(EventSettingsDefaultRenderBitRateChanged);
 }
+
+int settings_file_add_marker(SettingsFile *sf, double pos, const String &name) {
+    int index = 0;
+    while (index < sf->open_project_markers.length() && sf->open_project_markers.at(index).pos <= pos)
+        index += 1;
+    ok_or_panic(sf->open_project_markers.insert_space(index, 1));
+    SettingsFileMarker *marker = &sf->open_project_markers.at(index);
+    marker->pos = pos;
+    marker->name = name;
+    sf->events.trigger(EventSettingsMarkersChanged);
+    return index;
+}
+
+void settings_file_clear_markers(SettingsFile *sf) {
+    sf->open_project_markers.clear();
+    sf->events.trigger(EventSettingsMarkersChanged);
+}
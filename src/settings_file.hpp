@@ -15,9 +15,17 @@ enum SettingsFileState {
     SettingsFileStateEnd,
     SettingsFileStateReadyForProp,
     SettingsFileStateOpenProjectFile,
+    SettingsFileStateOpenProjectPlayHead,
+    SettingsFileStateOpenProjectScrollPos,
+    SettingsFileStateOpenProjectMarkers,
+    SettingsFileStateOpenProjectMarkerItem,
+    SettingsFileStateOpenProjectMarkerItemProp,
+    SettingsFileStateOpenProjectMarkerPos,
+    SettingsFileStateOpenProjectMarkerName,
     SettingsFileStateUserName,
     SettingsFileStateUserId,
     SettingsFileStateLatency,
+    SettingsFileStateSnapToZeroCrossing,
     SettingsFileStateExpectSampleDirs,
     SettingsFileStateSampleDirsItem,
     SettingsFileStatePerspectives,
@@ -56,6 +64,9 @@ enum SettingsFileState {
     SettingsFileStateDefaultRenderParamsProp,
     SettingsFileStateDefaultRenderParamsSampleFormat,
     SettingsFileStateDefaultRenderParamsBitRate,
+    SettingsFileStateKeyBindings,
+    SettingsFileStateKeyBindingProp,
+    SettingsFileStateKeyBindingValue,
 };
 
 enum SettingsFileDockType {
@@ -93,12 +104,32 @@ struct SettingsFileDeviceId {
     bool is_raw;
 };
 
+struct SettingsFileKeyBinding {
+    // see KeyBindingAction
+    ByteBuffer action;
+    // in the format of key_sequence_to_string. empty means unbound
+    ByteBuffer key_sequence;
+};
+
+struct SettingsFileMarker {
+    // in whole notes
+    double pos;
+    String name;
+};
+
 struct SettingsFile {
     // use this to announce when you change a setting (if anyone cares)
     EventDispatcher events;
 
     // settings you can directly manipulate
     uint256 open_project_id;
+    // play head position in the open project, restored on startup
+    double open_project_play_head;
+    // left edge of the track editor in the open project, in whole notes,
+    // restored on startup along with the play head
+    double open_project_scroll_pos;
+    // bookmarks in the open project, sorted by position
+    List<SettingsFileMarker> open_project_markers;
     String user_name;
     uint256 user_id;
     List<SettingsFileOpenWindow> open_windows;
@@ -107,9 +138,13 @@ struct SettingsFile {
     // index is DeviceId. if backend_name is NULL then that DeviceId is unspecified
     List<SettingsFileDeviceId> device_designations;
     double latency;
+    // seeking and selection edges in the track editor move to the nearest
+    // zero crossing
+    bool snap_to_zero_crossing;
     RenderFormatType default_render_format;
     SoundIoFormat default_render_sample_formats[RenderFormatTypeCount];
     int default_render_bit_rates[RenderFormatTypeCount];
+    List<SettingsFileKeyBinding> key_bindings;
 
     // private state
     ByteBuffer path;
@@ -119,6 +154,7 @@ struct SettingsFile {
     SettingsFileDock *current_dock;
     List<SettingsFileDock *> dock_stack;
     SettingsFileOpenWindow *current_open_window;
+    SettingsFileMarker *current_marker;
     DeviceId current_device_id;
     SettingsFileDeviceId *current_sf_device_id;
     RenderFormatType current_default_render_params_format;
@@ -141,4 +177,8 @@ void settings_file_set_default_render_sample_format(SettingsFile *sf,
 void settings_file_set_default_render_bit_rate(SettingsFile *sf,
         RenderFormatType format_type, int bit_rate);
 
+// inserts the marker in position order. returns the index of the new marker
+int settings_file_add_marker(SettingsFile *sf, double pos, const String &name);
+void settings_file_clear_markers(SettingsFile *sf);
+
 #endif
@@ -60,10 +60,12 @@ out vec4 FragColor;
 
 uniform sampler2D Tex;
 uniform vec4 Color;
+uniform float CoverageExponent;
 
 void main(void)
 {
-    FragColor = vec4(1, 1, 1, texture(Tex, FragTexCoord).r) * Color;
+    float coverage = pow(texture(Tex, FragTexCoord).r, CoverageExponent);
+    FragColor = vec4(1, 1, 1, coverage) * Color;
 }
 
 )FRAGMENT", NULL),
@@ -153,6 +155,50 @@ void main(void) {
     FragColor = ColorBottom * MixAmt + ColorTop * (1 - MixAmt);
 }
 
+)FRAGMENT", NULL),
+    rounded_rect_program(R"VERTEX(
+
+#version 150 core
+
+in vec3 VertexPosition;
+out vec2 FragPos;
+
+uniform mat4 MVP;
+uniform float Width;
+uniform float Height;
+
+void main(void) {
+    FragPos = VertexPosition.xy * vec2(Width, Height);
+    gl_Position = MVP * vec4(VertexPosition, 1.0);
+}
+
+)VERTEX", R"FRAGMENT(
+
+#version 150 core
+
+in vec2 FragPos;
+out vec4 FragColor;
+
+uniform float Width;
+uniform float Height;
+uniform float Radius;
+uniform float BorderWidth;
+uniform vec4 FillColor;
+uniform vec4 BorderColor;
+
+void main(void) {
+    // signed distance from the edge of the rounded rectangle, in pixels.
+    // fwidth keeps the anti-aliased edge one screen pixel wide at any scale
+    vec2 half_size = vec2(Width, Height) * 0.5;
+    vec2 q = abs(FragPos - half_size) - half_size + vec2(Radius);
+    float dist = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - Radius;
+    float aa = max(fwidth(dist), 0.0001);
+    float coverage = clamp(0.5 - dist / aa, 0.0, 1.0);
+    float fill_amt = clamp(0.5 - (dist + BorderWidth) / aa, 0.0, 1.0);
+    vec4 color = mix(BorderColor, FillColor, fill_amt);
+    FragColor = vec4(color.rgb, color.a * coverage);
+}
+
 )FRAGMENT", NULL)
 {
     _texture_attrib_tex_coord = _texture_shader_program.attrib_location("TexCoord");
@@ -165,6 +211,7 @@ void main(void) {
     _text_uniform_mvp = _text_shader_program.uniform_location("MVP");
     _text_uniform_tex = _text_shader_program.uniform_location("Tex");
     _text_uniform_color = _text_shader_program.uniform_location("Color");
+    _text_uniform_coverage_exponent = _text_shader_program.uniform_location("CoverageExponent");
 
     _primitive_attrib_position = _primitive_shader_program.attrib_location("VertexPosition");
     _primitive_uniform_mvp = _primitive_shader_program.uniform_location("MVP");
@@ -181,6 +228,15 @@ void main(void) {
     gradient_uniform_color_top = gradient_program.uniform_location("ColorTop");
     gradient_uniform_color_bottom = gradient_program.uniform_location("ColorBottom");
 
+    rounded_rect_attrib_position = rounded_rect_program.attrib_location("VertexPosition");
+    rounded_rect_uniform_mvp = rounded_rect_program.uniform_location("MVP");
+    rounded_rect_uniform_width = rounded_rect_program.uniform_location("Width");
+    rounded_rect_uniform_height = rounded_rect_program.uniform_location("Height");
+    rounded_rect_uniform_radius = rounded_rect_program.uniform_location("Radius");
+    rounded_rect_uniform_border_width = rounded_rect_program.uniform_location("BorderWidth");
+    rounded_rect_uniform_fill_color = rounded_rect_program.uniform_location("FillColor");
+    rounded_rect_uniform_border_color = rounded_rect_program.uniform_location("BorderColor");
+
     assert_no_gl_error();
 }
 
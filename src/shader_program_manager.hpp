@@ -23,6 +23,7 @@ public:
     GLint _text_uniform_mvp;
     GLint _text_uniform_tex;
     GLint _text_uniform_color;
+    GLint _text_uniform_coverage_exponent;
 
     ShaderProgram _primitive_shader_program;
     GLint _primitive_attrib_position;
@@ -41,6 +42,16 @@ public:
     GLint gradient_uniform_mvp;
     GLint gradient_uniform_color_top;
     GLint gradient_uniform_color_bottom;
+
+    ShaderProgram rounded_rect_program;
+    GLint rounded_rect_attrib_position;
+    GLint rounded_rect_uniform_mvp;
+    GLint rounded_rect_uniform_width;
+    GLint rounded_rect_uniform_height;
+    GLint rounded_rect_uniform_radius;
+    GLint rounded_rect_uniform_border_width;
+    GLint rounded_rect_uniform_fill_color;
+    GLint rounded_rect_uniform_border_color;
 };
 
 #endif
@@ -275,6 +275,127 @@ void String::make_upper_case() {
     }
 }
 
+// hangul syllables decompose and compose algorithmically rather than
+// through the tables
+static const uint32_t hangul_s_base = 0xac00;
+static const uint32_t hangul_l_base = 0x1100;
+static const uint32_t hangul_v_base = 0x1161;
+static const uint32_t hangul_t_base = 0x11a7;
+static const uint32_t hangul_l_count = 19;
+static const uint32_t hangul_v_count = 21;
+static const uint32_t hangul_t_count = 28;
+static const uint32_t hangul_n_count = hangul_v_count * hangul_t_count;
+static const uint32_t hangul_s_count = hangul_l_count * hangul_n_count;
+
+static const CanonicalDecomposition *find_decomposition(uint32_t c) {
+    int lo = 0;
+    int hi = array_length(canonical_decompositions);
+    while (lo < hi) {
+        int mid = lo + (hi - lo) / 2;
+        const CanonicalDecomposition *decomposition = &canonical_decompositions[mid];
+        if (c < decomposition->codepoint)
+            hi = mid;
+        else if (c > decomposition->codepoint)
+            lo = mid + 1;
+        else
+            return decomposition;
+    }
+    return nullptr;
+}
+
+static void decompose(uint32_t c, List<uint32_t> &out) {
+    if (c >= hangul_s_base && c < hangul_s_base + hangul_s_count) {
+        uint32_t s_index = c - hangul_s_base;
+        ok_or_panic(out.append(hangul_l_base + s_index / hangul_n_count));
+        ok_or_panic(out.append(hangul_v_base + (s_index % hangul_n_count) / hangul_t_count));
+        uint32_t t_index = s_index % hangul_t_count;
+        if (t_index != 0)
+            ok_or_panic(out.append(hangul_t_base + t_index));
+        return;
+    }
+    const CanonicalDecomposition *decomposition = find_decomposition(c);
+    if (!decomposition) {
+        ok_or_panic(out.append(c));
+        return;
+    }
+    decompose(decomposition->first, out);
+    if (decomposition->second)
+        decompose(decomposition->second, out);
+}
+
+// returns 0 if the pair has no primary composite
+static uint32_t compose(uint32_t first, uint32_t second) {
+    if (first >= hangul_l_base && first < hangul_l_base + hangul_l_count &&
+        second >= hangul_v_base && second < hangul_v_base + hangul_v_count)
+    {
+        return hangul_s_base + ((first - hangul_l_base) * hangul_v_count +
+                (second - hangul_v_base)) * hangul_t_count;
+    }
+    if (first >= hangul_s_base && first < hangul_s_base + hangul_s_count &&
+        (first - hangul_s_base) % hangul_t_count == 0 &&
+        second > hangul_t_base && second < hangul_t_base + hangul_t_count)
+    {
+        return first + (second - hangul_t_base);
+    }
+
+    int lo = 0;
+    int hi = array_length(canonical_compositions);
+    while (lo < hi) {
+        int mid = lo + (hi - lo) / 2;
+        const CanonicalComposition *composition = &canonical_compositions[mid];
+        if (first < composition->first || (first == composition->first && second < composition->second))
+            hi = mid;
+        else if (first > composition->first || second > composition->second)
+            lo = mid + 1;
+        else
+            return composition->composed;
+    }
+    return 0;
+}
+
+void String::normalize_nfc() {
+    List<uint32_t> chars;
+    for (int i = 0; i < _chars.length(); i += 1)
+        decompose(_chars.at(i), chars);
+
+    // canonical ordering: marks between two starters are sorted by their
+    // combining class, keeping the order of marks with the same class
+    for (int i = 1; i < chars.length(); i += 1) {
+        int class_i = combining_class(chars.at(i));
+        if (class_i == 0)
+            continue;
+        for (int j = i; j > 0; j -= 1) {
+            int class_prev = combining_class(chars.at(j - 1));
+            if (class_prev <= class_i)
+                break;
+            uint32_t tmp = chars.at(j - 1);
+            chars.at(j - 1) = chars.at(j);
+            chars.at(j) = tmp;
+        }
+    }
+
+    // canonical composition. a mark composes with the last starter unless
+    // a character of the same or higher class sits between them
+    _chars.clear();
+    int starter_index = -1;
+    int last_class = 0;
+    for (int i = 0; i < chars.length(); i += 1) {
+        uint32_t c = chars.at(i);
+        int c_class = combining_class(c);
+        if (starter_index >= 0) {
+            uint32_t composed = compose(_chars.at(starter_index), c);
+            if (composed && (last_class < c_class || last_class == 0)) {
+                _chars.at(starter_index) = composed;
+                continue;
+            }
+        }
+        if (c_class == 0)
+            starter_index = _chars.length();
+        last_class = c_class;
+        ok_or_panic(_chars.append(c));
+    }
+}
+
 uint32_t String::char_to_lower(uint32_t c) {
     return (c < array_length(unicode_characters)) ? unicode_characters[c].lower : c;
 }
@@ -291,6 +412,39 @@ bool String::is_whitespace(uint32_t c) {
     return false;
 }
 
+bool String::is_combining_mark(uint32_t c) {
+    // the ranges are sorted and do not overlap
+    int lo = 0;
+    int hi = array_length(combining_marks);
+    while (lo < hi) {
+        int mid = lo + (hi - lo) / 2;
+        const UnicodeRange *range = &combining_marks[mid];
+        if (c < range->first)
+            hi = mid;
+        else if (c > range->last)
+            lo = mid + 1;
+        else
+            return true;
+    }
+    return false;
+}
+
+int String::combining_class(uint32_t c) {
+    int lo = 0;
+    int hi = array_length(combining_classes);
+    while (lo < hi) {
+        int mid = lo + (hi - lo) / 2;
+        const CombiningClassRange *range = &combining_classes[mid];
+        if (c < range->first)
+            hi = mid;
+        else if (c > range->last)
+            lo = mid + 1;
+        else
+            return range->combining_class;
+    }
+    return 0;
+}
+
 void String::split_on_whitespace(List<String> &out) const {
     assert_no_err(out.resize(1));
     String *current = &out.at(0);
@@ -33,6 +33,9 @@ public:
 
     void make_lower_case();
     void make_upper_case();
+    // converts to Normalization Form C, so that precomposed characters and
+    // their decomposed forms, such as U+00E9 and 'e' U+0301, compare equal
+    void normalize_nfc();
 
     static const int max_codepoint = 0x1fffff;
     void append(uint32_t c) {
@@ -65,6 +68,11 @@ public:
     static uint32_t char_to_upper(uint32_t c);
 
     static bool is_whitespace(uint32_t c);
+    // nonspacing and enclosing marks such as U+0301 COMBINING ACUTE ACCENT,
+    // which are drawn over the preceding character
+    static bool is_combining_mark(uint32_t c);
+    // the canonical combining class from the unicode data. 0 for starters
+    static int combining_class(uint32_t c);
 
 private:
     List<uint32_t> _chars;
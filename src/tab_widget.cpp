@@ -59,7 +59,7 @@ void TabWidget::draw(const glm::mat4 &projection) {
         }
     }
 
-    current_tab->widget->draw(projection);
+    current_tab->widget->draw_clipped(projection);
 }
 
 void TabWidget::get_tab_pos(int index, int *x, int *y) {
@@ -208,6 +208,9 @@ void TabWidget::update_model() {
         current_tab->widget->top = top + widget_top;
         current_tab->widget->width = width;
         current_tab->widget->height = height - widget_top;
+        // keep a pane that draws past its edges, like a long label or a
+        // scrolled list, off the tab bar and the neighboring docks
+        current_tab->widget->set_clip(0, 0, current_tab->widget->width, current_tab->widget->height);
         current_tab->widget->on_resize();
     }
 }
@@ -52,6 +52,8 @@ TextWidget::TextWidget(GuiWindow *gui_window) :
     _on_text_change_event(default_on_text_change_event),
     _on_mouse_event(default_on_mouse_event)
 {
+    // the cursor indexes the text as typed
+    _label.set_normalize(false);
     update_model();
 }
 
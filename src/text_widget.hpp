@@ -24,6 +24,7 @@ public:
     void on_lose_focus() override;
     void on_text_input(const TextInputEvent *event) override;
     bool on_key_event(const KeyEvent *event) override;
+    bool focusable() const override { return _text_interaction_on; }
 
     int min_width() const override;
     int max_width() const override;
@@ -3,6 +3,7 @@
 #include "color.hpp"
 #include "gui_window.hpp"
 #include "label.hpp"
+#include "rich_label.hpp"
 #include "menu_widget.hpp"
 #include "scroll_bar_widget.hpp"
 #include "dragged_sample_file.hpp"
@@ -12,6 +13,26 @@
 static const int SEGMENT_PADDING = 2;
 static const int EXTRA_SCROLL_WIDTH = 200;
 static const int SEGMENT_TITLE_PADDING = 2;
+static const int MARKER_FLAG_SIZE = 6;
+static const int MARKER_LABEL_PADDING = 2;
+static const int SELECTION_EDGE_SIZE = 3;
+// play head speed in pixels per second when a seek key is first pressed, how
+// much it grows per second held, and the fastest it gets
+static const double SEEK_START_SPEED = 60.0;
+static const double SEEK_ACCELERATION = 600.0;
+static const double SEEK_MAX_SPEED = 3000.0;
+
+static const char *time_format_unit(TimeFormat time_format) {
+    switch (time_format) {
+        case TimeFormatSeconds: return "s";
+        case TimeFormatMinSec: return "m:s";
+        case TimeFormatMinSecMs: return "m:s.ms";
+        case TimeFormatFrames: return "fr";
+        case TimeFormatBarsBeats: return "bar";
+        case TimeFormatCount: break;
+    }
+    panic("invalid time format");
+}
 
 static void insert_track_before_handler(void *userdata) {
     TrackEditorWidget *track_editor_widget = (TrackEditorWidget *)userdata;
@@ -39,6 +60,33 @@ static void on_play_head_changed(Event, void *userdata) {
     track_editor_widget->update_play_head_model();
 }
 
+static void on_markers_changed(Event, void *userdata) {
+    TrackEditorWidget *track_editor_widget = (TrackEditorWidget *)userdata;
+    track_editor_widget->refresh_markers();
+    track_editor_widget->update_marker_model();
+}
+
+static void on_marker_click(void *userdata) {
+    TrackEditorWidget::DisplayMarker *display_marker = (TrackEditorWidget::DisplayMarker *)userdata;
+    TrackEditorWidget *track_editor_widget = display_marker->track_editor;
+    AudioGraph *audio_graph = track_editor_widget->audio_graph;
+    const SettingsFileMarker *marker = &audio_graph->settings_file->open_project_markers.at(display_marker->index);
+    audio_graph_set_play_head(audio_graph, marker->pos);
+}
+
+static void on_marker_hover(void *userdata, bool hovering) {
+    TrackEditorWidget::DisplayMarker *display_marker = (TrackEditorWidget::DisplayMarker *)userdata;
+    display_marker->hovered = hovering;
+}
+
+static void on_frame_update(Event, void *userdata) {
+    TrackEditorWidget *track_editor_widget = (TrackEditorWidget *)userdata;
+    track_editor_widget->seek_frame();
+    // the play head moves every frame while playing or seeking
+    if (track_editor_widget->seek_direction != 0 || audio_graph_is_playing(track_editor_widget->audio_graph))
+        track_editor_widget->gui->keep_animating();
+}
+
 static void scroll_callback(Event, void *userdata) {
     TrackEditorWidget *track_editor = (TrackEditorWidget *)userdata;
     track_editor->update_model();
@@ -61,6 +109,10 @@ TrackEditorWidget::TrackEditorWidget(GuiWindow *gui_window, AudioGraph *audio_gr
     menu_track(nullptr)
 {
     scrub_mouse_down = false;
+    seek_backward_key = no_shortcut();
+    seek_forward_key = no_shortcut();
+    seek_direction = 0;
+    seek_hold_time = 0.0;
 
     play_head_color = parse_color("#F47A28AA");
     play_head_icon = gui->img_play_head;
@@ -72,8 +124,22 @@ TrackEditorWidget::TrackEditorWidget(GuiWindow *gui_window, AudioGraph *audio_gr
     horiz_scroll_bar = create<ScrollBarWidget>(gui_window, ScrollBarLayoutHoriz);
     horiz_scroll_bar->parent_widget = this;
     pixels_per_whole_note = 100.0;
+    show_grid = true;
+    grid_color = color_grid_line();
+    marker_color = parse_color("#4FC1E9CC");
+    marker_hover_color = parse_color("#A0DCF4");
+    time_format = TimeFormatMinSec;
+    position_unit_color = color_light_disabled_text();
+    position_label = create<RichLabel>(gui);
+    have_selection = false;
+    selection_start = 0.0;
+    selection_end = 0.0;
+    selection_drag = SelectionDragNone;
+    selection_drag_anchor = 0.0;
+    selection_color = parse_color("#FFFFFF26");
 
     refresh_tracks();
+    refresh_markers();
     update_model();
 
     track_context_menu = create<MenuWidgetItem>(gui_window);
@@ -89,16 +155,21 @@ TrackEditorWidget::TrackEditorWidget(GuiWindow *gui_window, AudioGraph *audio_gr
     project->events.attach_handler(EventProjectTracksChanged, on_tracks_changed, this);
     project->events.attach_handler(EventProjectAudioClipSegmentsChanged, on_tracks_changed, this);
     audio_graph->events.attach_handler(EventAudioGraphPlayHeadChanged, on_play_head_changed, this);
+    audio_graph->settings_file->events.attach_handler(EventSettingsMarkersChanged, on_markers_changed, this);
     vert_scroll_bar->events.attach_handler(EventScrollValueChange, scroll_callback, this);
     horiz_scroll_bar->events.attach_handler(EventScrollValueChange, scroll_callback, this);
+    gui->events.attach_handler(EventFrameUpdate, on_frame_update, this);
 }
 
 TrackEditorWidget::~TrackEditorWidget() {
     project->events.detach_handler(EventProjectTracksChanged, on_tracks_changed);
     audio_graph->events.detach_handler(EventAudioGraphPlayHeadChanged, on_tracks_changed);
+    audio_graph->settings_file->events.detach_handler(EventSettingsMarkersChanged, on_markers_changed);
+    gui->events.detach_handler(EventFrameUpdate, on_frame_update);
 
     destroy(vert_scroll_bar, 1);
     destroy(horiz_scroll_bar, 1);
+    destroy(position_label, 1);
 
     for (int i = 0; i < gui_tracks.length(); i += 1) {
         destroy_gui_track(gui_tracks.at(i));
@@ -106,6 +177,12 @@ TrackEditorWidget::~TrackEditorWidget() {
     for (int i = 0; i < display_tracks.length(); i += 1) {
         destroy_display_track(display_tracks.at(i));
     }
+    for (int i = 0; i < display_markers.length(); i += 1) {
+        DisplayMarker *display_marker = display_markers.at(i);
+        gui_window->remove_hit_region(display_marker->hit_region_id);
+        destroy(display_marker->label, 1);
+        destroy(display_marker, 1);
+    }
 }
 
 void TrackEditorWidget::draw(const glm::mat4 &projection) {
@@ -132,6 +209,15 @@ void TrackEditorWidget::draw(const glm::mat4 &projection) {
         DisplayTrack *display_track = display_tracks.at(track_i);
 
         display_track->body_bg.draw(gui_window, projection);
+    }
+
+    if (show_grid) {
+        for (int i = 0; i < grid_line_models.length(); i += 1)
+            gui_window->fill_rect(grid_color, projection * grid_line_models.at(i));
+    }
+
+    for (int track_i = 0; track_i < display_track_count; track_i += 1) {
+        DisplayTrack *display_track = display_tracks.at(track_i);
 
         for (int segment_i = 0; segment_i < display_track->display_audio_clip_segment_count; segment_i += 1) {
             DisplayAudioClipSegment *segment = display_track->display_audio_clip_segments.at(segment_i);
@@ -178,6 +264,13 @@ void TrackEditorWidget::draw(const glm::mat4 &projection) {
     glStencilMask(0x00);
     glColorMask(GL_TRUE, GL_TRUE, GL_TRUE, GL_TRUE);
 
+    // additive so that the waveforms under the selection get brighter
+    // rather than washed out towards the selection color
+    if (have_selection) {
+        gui_window->set_blend_mode(BlendModeAdditive);
+        gui_window->fill_rect(selection_color, projection * selection_model);
+        gui_window->set_blend_mode(BlendModeNormal);
+    }
     gui_window->fill_rect(play_head_color, projection * play_head_model);
 
     glDisable(GL_STENCIL_TEST);
@@ -195,10 +288,20 @@ void TrackEditorWidget::draw(const glm::mat4 &projection) {
 
     timeline_bg.draw(gui_window, projection);
     gui_window->fill_rect(timeline_bottom_border_color, projection * timeline_bottom_border_model);
+    position_label->draw(projection * position_label_model);
+    for (int i = 0; i < display_markers.length(); i += 1) {
+        DisplayMarker *display_marker = display_markers.at(i);
+        if (!display_marker->visible)
+            continue;
+        const glm::vec4 &color = display_marker->hovered ? marker_hover_color : marker_color;
+        gui_window->fill_rect(color, projection * display_marker->line_model);
+        gui_window->fill_rect(color, projection * display_marker->flag_model);
+        display_marker->label->draw(projection * display_marker->label_model, track_name_color);
+    }
     gui->draw_image_color(gui_window, play_head_icon, projection * play_head_icon_model, play_head_color);
 
-    horiz_scroll_bar->draw(projection);
-    vert_scroll_bar->draw(projection);
+    horiz_scroll_bar->draw_clipped(projection);
+    vert_scroll_bar->draw_clipped(projection);
 }
 
 TrackEditorWidget::DisplayTrack * TrackEditorWidget::create_display_track(GuiTrack *gui_track) {
@@ -243,6 +346,16 @@ void TrackEditorWidget::destroy_display_audio_clip_segment(
     }
 }
 
+double TrackEditorWidget::scroll_pos() const {
+    return horiz_scroll_bar->value / pixels_per_whole_note;
+}
+
+void TrackEditorWidget::set_scroll_pos(double whole_note) {
+    // not set_value, which would clamp to the range of the current layout
+    horiz_scroll_bar->value = max(0, (int)(whole_note * pixels_per_whole_note));
+    update_model();
+}
+
 // these do not take scroll into account
 int TrackEditorWidget::whole_note_to_pixel(double whole_note_pos) {
     return body_left + pixels_per_whole_note * whole_note_pos;
@@ -263,6 +376,77 @@ void TrackEditorWidget::update_play_head_model() {
     int icon_top = timeline_bottom - ICON_HEIGHT;
     play_head_icon_model = transform2d(icon_left, icon_top, icon_scale_width, icon_scale_height);
     play_head_model = transform2d(play_head_x, timeline_bottom, 1, track_area_bottom - timeline_bottom);
+
+    ByteBuffer position_text;
+    project_format_position(project, play_head_pos, time_format, position_text);
+    position_label->clear();
+    position_label->add_span(position_text, gui->default_font_size, track_name_color);
+    ByteBuffer unit_text;
+    unit_text.format(" %s", time_format_unit(time_format));
+    position_label->add_span(unit_text, gui->default_font_size * 3 / 4, position_unit_color);
+    position_label->update();
+    int label_top = timeline_top + (timeline_height - position_label->height()) / 2;
+    position_label_model = transform2d(track_name_label_padding_left, label_top);
+}
+
+void TrackEditorWidget::set_time_format(TimeFormat new_time_format) {
+    time_format = new_time_format;
+    update_play_head_model();
+}
+
+void TrackEditorWidget::update_grid_model(int body_width) {
+    grid_line_models.clear();
+    int first_whole_note = ceil(pixel_to_whole_note(body_left + horiz_scroll_bar->value));
+    for (int whole_note = first_whole_note;; whole_note += 1) {
+        int x = whole_note_to_pixel(whole_note) - horiz_scroll_bar->value;
+        if (x >= body_left + body_width)
+            break;
+        ok_or_panic(grid_line_models.append(
+                    transform2d(x, timeline_bottom, 1, track_area_bottom - timeline_bottom)));
+    }
+}
+
+void TrackEditorWidget::refresh_markers() {
+    const List<SettingsFileMarker> &markers = audio_graph->settings_file->open_project_markers;
+    while (display_markers.length() > markers.length()) {
+        DisplayMarker *display_marker = display_markers.pop();
+        gui_window->remove_hit_region(display_marker->hit_region_id);
+        destroy(display_marker->label, 1);
+        destroy(display_marker, 1);
+    }
+    while (display_markers.length() < markers.length()) {
+        DisplayMarker *display_marker = create<DisplayMarker>();
+        display_marker->track_editor = this;
+        display_marker->index = display_markers.length();
+        display_marker->hovered = false;
+        display_marker->hit_region_id = gui_window->add_hit_region(this, 0, 0, 0, 0,
+                on_marker_click, on_marker_hover, display_marker);
+        display_marker->label = create<Label>(gui);
+        ok_or_panic(display_markers.append(display_marker));
+    }
+    for (int i = 0; i < markers.length(); i += 1) {
+        Label *label = display_markers.at(i)->label;
+        label->set_text(markers.at(i).name);
+        label->update();
+    }
+}
+
+void TrackEditorWidget::update_marker_model() {
+    const List<SettingsFileMarker> &markers = audio_graph->settings_file->open_project_markers;
+    for (int i = 0; i < display_markers.length(); i += 1) {
+        DisplayMarker *display_marker = display_markers.at(i);
+        int x = whole_note_to_pixel(markers.at(i).pos) - horiz_scroll_bar->value;
+        display_marker->visible = (x >= body_left && x < width);
+        display_marker->line_model = transform2d(x, timeline_top, 1, timeline_height);
+        display_marker->flag_model = transform2d(x, timeline_top, MARKER_FLAG_SIZE, MARKER_FLAG_SIZE);
+        int label_left = x + MARKER_FLAG_SIZE + MARKER_LABEL_PADDING;
+        int label_top = timeline_top + (timeline_height - display_marker->label->height()) / 2;
+        display_marker->label_model = transform2d(label_left, label_top);
+
+        int hit_width = display_marker->visible ? label_left + display_marker->label->width() - x : 0;
+        gui_window->set_hit_region_rect(display_marker->hit_region_id,
+                left + x, top + timeline_top, hit_width, timeline_height);
+    }
 }
 
 void TrackEditorWidget::update_model() {
@@ -337,6 +521,7 @@ void TrackEditorWidget::update_model() {
     horiz_scroll_bar->set_value(horiz_scroll_bar->value);
     horiz_scroll_bar->on_resize();
 
+    update_grid_model(body_width);
 
     // now consider scroll position and create display tracks for tracks that
     // are visible
@@ -439,6 +624,59 @@ void TrackEditorWidget::update_model() {
     }
 
     update_play_head_model();
+    update_marker_model();
+    update_selection_model();
+}
+
+void TrackEditorWidget::update_selection_model() {
+    int start_x = whole_note_to_pixel(selection_start) - horiz_scroll_bar->value;
+    int end_x = whole_note_to_pixel(selection_end) - horiz_scroll_bar->value;
+    selection_model = transform2d(start_x, timeline_bottom,
+            max(1, end_x - start_x), track_area_bottom - timeline_bottom);
+}
+
+TrackEditorWidget::SelectionDrag TrackEditorWidget::selection_hit_test(int x, int y) {
+    if (y <= timeline_bottom || y >= track_area_bottom || x < body_left)
+        return SelectionDragNone;
+    if (have_selection) {
+        int start_x = whole_note_to_pixel(selection_start) - horiz_scroll_bar->value;
+        int end_x = whole_note_to_pixel(selection_end) - horiz_scroll_bar->value;
+        if (abs_diff(x, start_x) <= SELECTION_EDGE_SIZE)
+            return SelectionDragStart;
+        if (abs_diff(x, end_x) <= SELECTION_EDGE_SIZE)
+            return SelectionDragEnd;
+        if (x > start_x && x < end_x)
+            return SelectionDragMove;
+    }
+    return SelectionDragNew;
+}
+
+void TrackEditorWidget::drag_selection(const MouseEvent *event) {
+    double mouse_pos = max(0.0, pixel_to_whole_note(event->x + horiz_scroll_bar->value));
+    // a move snaps the selection start rather than the mouse
+    double whole_note = (selection_drag == SelectionDragMove) ? mouse_pos : snap(mouse_pos);
+    switch (selection_drag) {
+        case SelectionDragNone:
+            return;
+        case SelectionDragNew:
+            selection_start = min(selection_drag_anchor, whole_note);
+            selection_end = max(selection_drag_anchor, whole_note);
+            break;
+        case SelectionDragMove:
+            {
+                double length = selection_end - selection_start;
+                selection_start = snap(max(0.0, whole_note - selection_drag_anchor));
+                selection_end = selection_start + length;
+                break;
+            }
+        case SelectionDragStart:
+            selection_start = min(whole_note, selection_end);
+            break;
+        case SelectionDragEnd:
+            selection_end = max(whole_note, selection_start);
+            break;
+    }
+    update_selection_model();
 }
 
 TrackEditorWidget::GuiTrack * TrackEditorWidget::create_gui_track() {
@@ -466,8 +704,65 @@ void TrackEditorWidget::destroy_gui_track(GuiTrack *gui_track) {
     }
 }
 
+double TrackEditorWidget::snap(double whole_note) {
+    if (!audio_graph->settings_file->snap_to_zero_crossing)
+        return whole_note;
+    return project_snap_to_zero_crossing(project, whole_note);
+}
+
 void TrackEditorWidget::scrub(const MouseEvent *event) {
-    double whole_note = pixel_to_whole_note(event->x + horiz_scroll_bar->value);
+    double whole_note = snap(pixel_to_whole_note(event->x + horiz_scroll_bar->value));
+    audio_graph_set_play_head(audio_graph, whole_note);
+}
+
+void TrackEditorWidget::hold_seek_key(int direction, const KeyEvent *event) {
+    if (event->action == KeyActionDown) {
+        // key repeat sends more down events while the key is held; only the
+        // first one restarts the acceleration
+        if (seek_direction != direction) {
+            seek_direction = direction;
+            seek_hold_time = 0.0;
+        }
+    } else if (seek_direction == direction) {
+        seek_direction = 0;
+        // snap once the key is let go. snapping every frame would keep
+        // pulling a slow seek back to the same crossing
+        double whole_note = audio_graph_play_head_pos(audio_graph);
+        audio_graph_set_play_head(audio_graph, snap(whole_note));
+    }
+}
+
+bool TrackEditorWidget::on_key_event(const KeyEvent *event) {
+    // match releases on the key alone, in case a modifier was let go first
+    if (!null_key_sequence(seek_backward_key) && event->virt_key == seek_backward_key.key &&
+        (event->action == KeyActionUp || key_sequence_match(seek_backward_key, event)))
+    {
+        hold_seek_key(-1, event);
+        return true;
+    }
+    if (!null_key_sequence(seek_forward_key) && event->virt_key == seek_forward_key.key &&
+        (event->action == KeyActionUp || key_sequence_match(seek_forward_key, event)))
+    {
+        hold_seek_key(1, event);
+        return true;
+    }
+    return false;
+}
+
+void TrackEditorWidget::on_lose_focus() {
+    // the key up would go to whichever widget has focus now
+    seek_direction = 0;
+}
+
+void TrackEditorWidget::seek_frame() {
+    if (seek_direction == 0)
+        return;
+    double dt = gui->delta_time;
+    double speed = min(SEEK_START_SPEED + SEEK_ACCELERATION * seek_hold_time, SEEK_MAX_SPEED);
+    seek_hold_time += dt;
+
+    double delta = seek_direction * speed * dt / pixels_per_whole_note;
+    double whole_note = max(0.0, audio_graph_play_head_pos(audio_graph) + delta);
     audio_graph_set_play_head(audio_graph, whole_note);
 }
 
@@ -480,10 +775,23 @@ void TrackEditorWidget::on_mouse_move(const MouseEvent *event) {
         }
         return;
     }
+    if (selection_drag != SelectionDragNone) {
+        if (event->button == MouseButtonLeft && event->action == MouseActionUp) {
+            selection_drag = SelectionDragNone;
+            // a click without a drag clears the selection
+            if (selection_end <= selection_start)
+                have_selection = false;
+            update_cursor(event->x, event->y);
+        } else if (event->action == MouseActionMove) {
+            drag_selection(event);
+        }
+        return;
+    }
     if (forward_mouse_event(vert_scroll_bar, event))
         return;
     if (forward_mouse_event(horiz_scroll_bar, event))
         return;
+    update_cursor(event->x, event->y);
     if (event->button == MouseButtonRight && event->action == MouseActionDown) {
         GuiTrack *gui_track = get_track_head_at(event->x, event->y);
         if (gui_track)
@@ -491,14 +799,47 @@ void TrackEditorWidget::on_mouse_move(const MouseEvent *event) {
         return;
     }
     if (event->button == MouseButtonLeft && event->action == MouseActionDown) {
-        if (event->y >= timeline_top && event->y <= timeline_bottom) {
+        if (event->y >= timeline_top && event->y <= timeline_bottom && event->x < body_left) {
+            set_time_format((TimeFormat)((time_format + 1) % TimeFormatCount));
+        } else if (event->y >= timeline_top && event->y <= timeline_bottom) {
             scrub_mouse_down = true;
             scrub(event);
+        } else {
+            selection_drag = selection_hit_test(event->x, event->y);
+            double whole_note = max(0.0, pixel_to_whole_note(event->x + horiz_scroll_bar->value));
+            if (selection_drag == SelectionDragNew) {
+                // snap the anchor like the edge it is dragged against
+                double anchor = snap(whole_note);
+                have_selection = true;
+                selection_start = anchor;
+                selection_end = anchor;
+                selection_drag_anchor = anchor;
+                update_selection_model();
+            } else if (selection_drag == SelectionDragMove) {
+                selection_drag_anchor = whole_note - selection_start;
+            }
         }
         return;
     }
 }
 
+void TrackEditorWidget::on_mouse_out(const MouseEvent *event) {
+    gui_window->set_cursor_default();
+}
+
+void TrackEditorWidget::update_cursor(int x, int y) {
+    // the timeline scrubs and the position readout cycles formats on click
+    if (y >= timeline_top && y <= timeline_bottom) {
+        gui_window->set_cursor_hand();
+        return;
+    }
+    SelectionDrag hit = selection_hit_test(x, y);
+    if (hit == SelectionDragStart || hit == SelectionDragEnd)
+        gui_window->set_cursor_hresize();
+    else
+        gui_window->set_cursor_default();
+}
+
 TrackEditorWidget::GuiTrack * TrackEditorWidget::get_track_head_at(int x, int y) {
     for (int i = 0; i < display_tracks.length(); i += 1) {
         DisplayTrack *display_track = display_tracks.at(i);
@@ -3,11 +3,13 @@
 
 #include "widget.hpp"
 #include "sunken_box.hpp"
+#include "project.hpp"
 
 struct Project;
 struct AudioGraph;
 struct Track;
 class Label;
+class RichLabel;
 class MenuWidgetItem;
 class ScrollBarWidget;
 struct DraggedSampleFile;
@@ -24,8 +26,12 @@ public:
     void draw(const glm::mat4 &projection) override;
     void on_resize() override { update_model(); }
     void on_mouse_move(const MouseEvent *event) override;
+    void on_mouse_out(const MouseEvent *event) override;
     void on_mouse_wheel(const MouseWheelEvent *event) override;
     void on_drag(const DragEvent *) override;
+    bool on_key_event(const KeyEvent *event) override;
+    void on_lose_focus() override;
+    bool focusable() const override { return true; }
 
 
     AudioGraph *audio_graph;
@@ -63,6 +69,62 @@ public:
 
     double pixels_per_whole_note;
 
+    // left edge of the visible part of the timeline, in whole notes. a
+    // position set before the first layout is clamped by that layout
+    double scroll_pos() const;
+    void set_scroll_pos(double whole_note);
+
+    // play head position, shown in the timeline above the track heads and
+    // followed by the unit of the time format in smaller text. clicking it
+    // cycles through the time formats
+    TimeFormat time_format;
+    glm::vec4 position_unit_color;
+    RichLabel *position_label;
+    glm::mat4 position_label_model;
+
+    // vertical lines at each whole note, drawn behind the audio clip segments
+    bool show_grid;
+    glm::vec4 grid_color;
+    List<glm::mat4> grid_line_models;
+
+    // a flag in the timeline for each marker in the open project
+    struct DisplayMarker {
+        TrackEditorWidget *track_editor;
+        // into the settings file's open_project_markers
+        int index;
+        // clicking the flag or name moves the play head to the marker
+        int hit_region_id;
+        bool hovered;
+        Label *label;
+        glm::mat4 line_model;
+        glm::mat4 flag_model;
+        glm::mat4 label_model;
+        bool visible;
+    };
+    glm::vec4 marker_color;
+    glm::vec4 marker_hover_color;
+    List<DisplayMarker *> display_markers;
+
+    // time range selection across all tracks. dragging in the track area
+    // makes a new one, dragging inside it moves it and dragging within a
+    // few pixels of either edge resizes it
+    enum SelectionDrag {
+        SelectionDragNone,
+        SelectionDragNew,
+        SelectionDragMove,
+        SelectionDragStart,
+        SelectionDragEnd,
+    };
+    bool have_selection;
+    double selection_start;
+    double selection_end;
+    SelectionDrag selection_drag;
+    // where the drag began for a new selection, or the offset from the
+    // selection start for a move
+    double selection_drag_anchor;
+    glm::vec4 selection_color;
+    glm::mat4 selection_model;
+
     struct DisplayAudioClipSegment;
     struct GuiAudioClipSegment {
         AudioClipSegment *segment;
@@ -126,8 +188,21 @@ public:
 
     bool scrub_mouse_down;
 
+    // holding a seek key moves the play head every frame, starting slow for
+    // fine adjustment and speeding up the longer the key is held. set by
+    // GenesisEditor from the seek_backward and seek_forward key bindings
+    KeySequence seek_backward_key;
+    KeySequence seek_forward_key;
+    // -1 while the backward key is held, 1 for forward, 0 for neither
+    int seek_direction;
+    double seek_hold_time;
+
     void update_model();
     void update_play_head_model();
+    void set_time_format(TimeFormat new_time_format);
+    void update_grid_model(int body_width);
+    void update_marker_model();
+    void refresh_markers();
     GuiTrack *create_gui_track();
     DisplayTrack * create_display_track(GuiTrack *gui_track);
     DisplayAudioClipSegment * create_display_audio_clip_segment(
@@ -147,7 +222,14 @@ public:
     void destroy_gui_audio_clip_segment(GuiAudioClipSegment *gui_audio_clip_segment);
     int whole_note_to_pixel(double whole_note_pos);
     double pixel_to_whole_note(int pixel_x);
+    double snap(double whole_note);
     void scrub(const MouseEvent *event);
+    void hold_seek_key(int direction, const KeyEvent *event);
+    void seek_frame();
+    void update_cursor(int x, int y);
+    void update_selection_model();
+    SelectionDrag selection_hit_test(int x, int y);
+    void drag_selection(const MouseEvent *event);
 };
 
 #endif
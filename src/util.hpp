@@ -182,6 +182,14 @@ static inline T sign(T x) {
         return 0;
 }
 
+// the smallest power of 2 that is at least x, for x >= 1
+static inline int next_power_of_two(int x) {
+    int result = 1;
+    while (result < x)
+        result *= 2;
+    return result;
+}
+
 template<typename T, int(*Comparator)(T, T)>
 void quick_sort(T *in_place_list, int size) {
     auto qsort_comparator = [](const void *void_a, const void *void_b) -> int {
@@ -10,7 +10,9 @@ Widget::Widget(GuiWindow *gui_window) :
     top(0),
     width(100),
     height(100),
-    is_visible(true)
+    is_visible(true),
+    drawn_frame(-1),
+    has_clip(false)
 {
     layout_row = -1;
 }
@@ -25,6 +27,83 @@ void Widget::remove_widget(Widget *widget) {
     panic("unimplemented");
 }
 
+void Widget::set_clip(int clip_left, int clip_top, int clip_width, int clip_height) {
+    has_clip = true;
+    this->clip_left = clip_left;
+    this->clip_top = clip_top;
+    this->clip_width = clip_width;
+    this->clip_height = clip_height;
+}
+
+void Widget::clear_clip() {
+    has_clip = false;
+}
+
+void Widget::draw_clipped(const glm::mat4 &projection) {
+    // skip widgets entirely outside the visible area. empty widgets that
+    // touch its edge still draw
+    int visible_left = max(left, gui_window->_cull_left);
+    int visible_top = max(top, gui_window->_cull_top);
+    int visible_right = min(left + width, gui_window->_cull_right);
+    int visible_bottom = min(top + height, gui_window->_cull_bottom);
+    if (has_clip) {
+        visible_left = max(visible_left, left + clip_left);
+        visible_top = max(visible_top, top + clip_top);
+        visible_right = min(visible_right, left + clip_left + clip_width);
+        visible_bottom = min(visible_bottom, top + clip_top + clip_height);
+    }
+    if (visible_right < visible_left || visible_bottom < visible_top) {
+        gui_window->_frame_culled_widget_count += 1;
+        return;
+    }
+    gui_window->_frame_drawn_widget_count += 1;
+    drawn_frame = gui_window->_frame_index;
+
+    if (!has_clip) {
+        draw(projection);
+        return;
+    }
+
+    GLboolean prev_enabled = glIsEnabled(GL_SCISSOR_TEST);
+    GLint prev_box[4];
+    glGetIntegerv(GL_SCISSOR_BOX, prev_box);
+
+    // scissor coordinates are framebuffer pixels from the bottom left
+    int x0 = gui_window->logical_to_framebuffer(left + clip_left);
+    int x1 = x0 + gui_window->logical_to_framebuffer(clip_width);
+    int y0 = gui_window->logical_to_framebuffer_y(top + clip_top + clip_height);
+    int y1 = y0 + gui_window->logical_to_framebuffer(clip_height);
+    if (prev_enabled) {
+        x0 = max(x0, prev_box[0]);
+        y0 = max(y0, prev_box[1]);
+        x1 = min(x1, prev_box[0] + prev_box[2]);
+        y1 = min(y1, prev_box[1] + prev_box[3]);
+    }
+
+    glEnable(GL_SCISSOR_TEST);
+    glScissor(x0, y0, max(0, x1 - x0), max(0, y1 - y0));
+
+    int prev_cull_left = gui_window->_cull_left;
+    int prev_cull_top = gui_window->_cull_top;
+    int prev_cull_right = gui_window->_cull_right;
+    int prev_cull_bottom = gui_window->_cull_bottom;
+    gui_window->_cull_left = visible_left;
+    gui_window->_cull_top = visible_top;
+    gui_window->_cull_right = visible_right;
+    gui_window->_cull_bottom = visible_bottom;
+
+    draw(projection);
+
+    gui_window->_cull_left = prev_cull_left;
+    gui_window->_cull_top = prev_cull_top;
+    gui_window->_cull_right = prev_cull_right;
+    gui_window->_cull_bottom = prev_cull_bottom;
+
+    glScissor(prev_box[0], prev_box[1], prev_box[2], prev_box[3]);
+    if (!prev_enabled)
+        glDisable(GL_SCISSOR_TEST);
+}
+
 void Widget::on_size_hints_changed() {
     if (parent_widget) {
         parent_widget->on_resize();
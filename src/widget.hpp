@@ -17,6 +17,15 @@ public:
     virtual ~Widget();
     virtual void draw(const glm::mat4 &projection) = 0;
 
+    // draw() with the clip rectangle applied, if any. containers call this
+    // on their children rather than draw().
+    void draw_clipped(const glm::mat4 &projection);
+
+    // restrict drawing to a rectangle relative to the widget, using the GL
+    // scissor test. nested clips are intersected.
+    void set_clip(int clip_left, int clip_top, int clip_width, int clip_height);
+    void clear_clip();
+
     // default no minimum, no maximum
     virtual int min_width() const { return 0; }
     virtual int max_width() const { return -1; }
@@ -39,6 +48,10 @@ public:
     virtual void on_resize() {}
     virtual void remove_widget(Widget *widget);
 
+    // whether to draw a focus ring around this widget while it has focus.
+    // true for widgets that do something with the keyboard
+    virtual bool focusable() const { return false; }
+
 
     GuiWindow *gui_window;
     Widget *parent_widget;
@@ -53,6 +66,14 @@ public:
     int layout_row;
     int layout_col;
     bool is_visible;
+    // GuiWindow::_frame_index of the frame this was last drawn in
+    int drawn_frame;
+
+    bool has_clip;
+    int clip_left;
+    int clip_top;
+    int clip_width;
+    int clip_height;
 
     // convenience methods
     glm::mat4 transform2d(int left, int top, float scale_x, float scale_y);
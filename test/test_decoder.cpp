@@ -0,0 +1,73 @@
+#include "test_decoder.hpp"
+#include "util.hpp"
+
+#include <math.h>
+
+static const uint32_t noise_seed = 0x9e3779b9;
+
+static int test_decoder_open(struct GenesisAudioFileDecoder *decoder, const char *filename,
+        struct GenesisAudioFile *audio_file)
+{
+    TestDecoder *td = (TestDecoder *)decoder->userdata;
+    td->position = 0;
+    init_random_state(&td->random_state, noise_seed);
+    genesis_audio_file_set_sample_rate(audio_file, td->sample_rate);
+    SoundIoChannelLayoutId layout_id = td->stereo ? SoundIoChannelLayoutIdStereo : SoundIoChannelLayoutIdMono;
+    return genesis_audio_file_set_channel_layout(audio_file, soundio_channel_layout_get_builtin(layout_id));
+}
+
+static float test_decoder_sample(TestDecoder *td, long frame) {
+    double cycles = frame * td->frequency / td->sample_rate;
+    double phase = cycles - floor(cycles);
+    switch (td->waveform) {
+        case TestWaveformSine:
+            return sin(phase * 2.0 * M_PI);
+        case TestWaveformSquare:
+            return (phase < 0.5) ? 1.0f : -1.0f;
+        case TestWaveformNoise:
+            return get_random(&td->random_state) / (double)UINT32_MAX * 2.0 - 1.0;
+        case TestWaveformRamp:
+            return phase * 2.0 - 1.0;
+    }
+    panic("invalid waveform");
+}
+
+static int test_decoder_read_buffer(struct GenesisAudioFileDecoder *decoder, float **channel_buffers,
+        int max_frame_count, int *out_frame_count)
+{
+    TestDecoder *td = (TestDecoder *)decoder->userdata;
+    int frame_count = min((long)max_frame_count, td->frame_count - td->position);
+    int channel_count = td->stereo ? 2 : 1;
+    for (int i = 0; i < frame_count; i += 1) {
+        float sample = test_decoder_sample(td, td->position + i);
+        for (int ch = 0; ch < channel_count; ch += 1)
+            channel_buffers[ch][i] = sample;
+    }
+    td->position += frame_count;
+    *out_frame_count = frame_count;
+    return 0;
+}
+
+static double test_decoder_duration(struct GenesisAudioFileDecoder *decoder) {
+    TestDecoder *td = (TestDecoder *)decoder->userdata;
+    return td->frame_count / (double)td->sample_rate;
+}
+
+static void test_decoder_close(struct GenesisAudioFileDecoder *decoder) {
+}
+
+void test_decoder_init(TestDecoder *td, TestWaveform waveform, double frequency,
+        int sample_rate, double seconds)
+{
+    td->decoder.userdata = td;
+    td->decoder.open = test_decoder_open;
+    td->decoder.read_buffer = test_decoder_read_buffer;
+    td->decoder.duration = test_decoder_duration;
+    td->decoder.close = test_decoder_close;
+    td->waveform = waveform;
+    td->frequency = frequency;
+    td->sample_rate = sample_rate;
+    td->frame_count = lround(seconds * sample_rate);
+    td->stereo = false;
+    td->position = 0;
+}
@@ -0,0 +1,34 @@
+#ifndef TEST_DECODER_HPP
+#define TEST_DECODER_HPP
+
+#include "genesis.h"
+#include "random.hpp"
+
+enum TestWaveform {
+    TestWaveformSine,
+    TestWaveformSquare,
+    TestWaveformNoise,
+    TestWaveformRamp,
+};
+
+// a GenesisAudioFileDecoder that makes up its samples instead of reading a
+// file, so tests know exactly what a load returns. every channel gets the
+// same full scale waveform at frequency hertz, or white noise from a fixed
+// seed. the filename passed to genesis_audio_file_load is ignored. mono
+// unless stereo is set after test_decoder_init.
+struct TestDecoder {
+    GenesisAudioFileDecoder decoder;
+    TestWaveform waveform;
+    double frequency;
+    int sample_rate;
+    long frame_count;
+    bool stereo;
+
+    long position;
+    RandomState random_state;
+};
+
+void test_decoder_init(TestDecoder *test_decoder, TestWaveform waveform, double frequency,
+        int sample_rate, double seconds);
+
+#endif
@@ -7,6 +7,7 @@
 #include "ring_buffer_test.hpp"
 #include "error.h"
 #include "thread_safe_queue_test.hpp"
+#include "test_decoder.hpp"
 #include "sort_key.hpp"
 #include "locked_queue.hpp"
 #include "crc32.hpp"
@@ -15,8 +16,18 @@
 #include "settings_file.hpp"
 #include "project.hpp"
 #include "genesis.h"
+#include "audio_file.hpp"
+#include "key_bindings.hpp"
 #include "atomic_value.hpp"
 #include "atomic_double.hpp"
+#include "notdef_box.hpp"
+#include "redraw_state.hpp"
+#include "fixed_timestep.hpp"
+#include "modal_input.hpp"
+#include "rich_text_layout.hpp"
+#include "label_layout.hpp"
+#include "label_filter.hpp"
+#include "font_library.hpp"
 
 #include <stdio.h>
 #include <assert.h>
@@ -105,6 +116,22 @@ static void test_parse_color(void) {
     assert_floats_close(color[3], 1.0f);
 }
 
+static void test_color_contrast_ratio(void) {
+    glm::vec4 black = parse_color("#000000");
+    glm::vec4 white = parse_color("#FFFFFF");
+    assert(fabsf(color_contrast_ratio(black, white) - 21.0f) < 0.01f);
+    assert(fabsf(color_contrast_ratio(white, black) - 21.0f) < 0.01f);
+    assert(fabsf(color_contrast_ratio(white, white) - 1.0f) < 0.01f);
+
+    // the high contrast theme meets WCAG AAA for text on its backgrounds
+    color_high_contrast = true;
+    assert(color_contrast_ratio(color_fg_text(), color_dark_bg()) >= 7.0f);
+    assert(color_contrast_ratio(color_fg_text(), color_dark_bg_alt()) >= 7.0f);
+    assert(color_contrast_ratio(color_fg_text(), color_selection()) >= 7.0f);
+    assert(color_contrast_ratio(color_dark_text(), color_light_bg()) >= 7.0f);
+    color_high_contrast = false;
+}
+
 static void test_euclidean_mod(void) {
     assert(euclidean_mod(3, 5) == 3);
     assert(euclidean_mod(8, 5) == 3);
@@ -112,6 +139,14 @@ static void test_euclidean_mod(void) {
     assert(euclidean_mod(-6, 5) == 4);
 }
 
+static void test_next_power_of_two(void) {
+    assert(next_power_of_two(1) == 1);
+    assert(next_power_of_two(2) == 2);
+    assert(next_power_of_two(3) == 4);
+    assert(next_power_of_two(100) == 128);
+    assert(next_power_of_two(1024) == 1024);
+}
+
 static void test_gcd(void) {
     assert(greatest_common_denominator(42, 56) == 14);
     assert(greatest_common_denominator(54, 24) == 6);
@@ -277,6 +312,19 @@ static void test_settings_file(void) {
     perspective->dock.dock_type = SettingsFileDockTypeTabs;
     ok_or_panic(perspective->dock.tabs.append("Fun Tab"));
 
+    assert(settings_file_add_marker(sf, 4.0, "Chorus") == 0);
+    assert(settings_file_add_marker(sf, 1.5, "Verse") == 0);
+
+    ok_or_panic(sf->key_bindings.add_one());
+    sf->key_bindings.last().action = "undo";
+    sf->key_bindings.last().key_sequence = "Ctrl+Shift+Z";
+
+    assert(!sf->snap_to_zero_crossing);
+    sf->snap_to_zero_crossing = true;
+
+    sf->open_project_play_head = 6.5;
+    sf->open_project_scroll_pos = 12.25;
+
     settings_file_commit(sf);
     settings_file_close(sf);
 
@@ -289,6 +337,17 @@ static void test_settings_file(void) {
     assert(perspective->dock.dock_type == SettingsFileDockTypeTabs);
     assert(perspective->dock.tabs.length() == 1);
     assert(String::compare(perspective->dock.tabs.last(), "Fun Tab") == 0);
+    assert(sf->open_project_markers.length() == 2);
+    assert(sf->open_project_markers.at(0).pos == 1.5);
+    assert(String::compare(sf->open_project_markers.at(0).name, "Verse") == 0);
+    assert(sf->open_project_markers.at(1).pos == 4.0);
+    assert(String::compare(sf->open_project_markers.at(1).name, "Chorus") == 0);
+    assert(sf->key_bindings.length() == 1);
+    assert(ByteBuffer::compare(sf->key_bindings.at(0).action, "undo") == 0);
+    assert(ByteBuffer::compare(sf->key_bindings.at(0).key_sequence, "Ctrl+Shift+Z") == 0);
+    assert(sf->snap_to_zero_crossing);
+    assert(sf->open_project_play_head == 6.5);
+    assert(sf->open_project_scroll_pos == 12.25);
 
     settings_file_close(sf);
 
@@ -390,6 +449,113 @@ static void test_basic_project_editing(void) {
     genesis_context_destroy(context);
 }
 
+static void test_project_duration_sample_rate(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+    static const char *tmp_proj_path = "/tmp/test_genesis_duration.gdaw";
+    os_delete(tmp_proj_path);
+
+    User *user = user_create(uint256::random(), os_get_user_name());
+    Project *project;
+    ok_or_panic(project_create(context, tmp_proj_path, uint256::random(), user, &project));
+
+    AudioAsset *audio_asset;
+    ok_or_panic(project_add_audio_asset(project, "../test/tiny-sine.ogg", &audio_asset));
+    project_add_audio_clip(project, audio_asset);
+    AudioClip *audio_clip = project->audio_clip_list.last();
+
+    // tiny-sine.ogg is 48000 Hz and the project is not
+    int clip_sample_rate = project_audio_clip_sample_rate(project, audio_clip);
+    assert(clip_sample_rate != project->sample_rate);
+
+    long frame_count = project_audio_clip_frame_count(project, audio_clip);
+    project_add_audio_clip_segment(project, audio_clip, project->track_list.at(0), 0, frame_count, 0.0);
+
+    double expected_frames = frame_count * (project->sample_rate / (double)clip_sample_rate);
+    assert(fabs(project_get_duration_frames(project) - expected_frames) <= 1.0);
+
+    ByteBuffer asset_path;
+    os_path_join(asset_path, os_path_dirname(tmp_proj_path), audio_asset->path);
+
+    project_close(project);
+    user_destroy(user);
+    os_delete(asset_path.raw());
+    os_delete(tmp_proj_path);
+    genesis_context_destroy(context);
+}
+
+static void test_project_snap_to_zero_crossing(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+    static const char *tmp_proj_path = "/tmp/test_genesis_snap.gdaw";
+    os_delete(tmp_proj_path);
+
+    User *user = user_create(uint256::random(), os_get_user_name());
+    Project *project;
+    ok_or_panic(project_create(context, tmp_proj_path, uint256::random(), user, &project));
+
+    // nothing to snap to without audio
+    assert(project_snap_to_zero_crossing(project, 0.5) == 0.5);
+
+    AudioAsset *audio_asset;
+    ok_or_panic(project_add_audio_asset(project, "../test/tiny-sine.ogg", &audio_asset));
+    project_add_audio_clip(project, audio_asset);
+    AudioClip *audio_clip = project->audio_clip_list.last();
+    long frame_count = project_audio_clip_frame_count(project, audio_clip);
+    project_add_audio_clip_segment(project, audio_clip, project->track_list.at(0), 0, frame_count, 0.0);
+
+    // the selection anchor and the edge dragged against it both snap, so a
+    // click without a drag leaves an empty selection. that needs snapping
+    // an already snapped position to leave it where it is.
+    double whole_note = project_get_duration_whole_notes(project) / 2.0;
+    double anchor = project_snap_to_zero_crossing(project, whole_note);
+    assert(fabs(anchor - whole_note) <= 0.010 * 140.0 / 60.0);
+    assert_floats_close(project_snap_to_zero_crossing(project, anchor), anchor);
+
+    ByteBuffer asset_path;
+    os_path_join(asset_path, os_path_dirname(tmp_proj_path), audio_asset->path);
+
+    project_close(project);
+    user_destroy(user);
+    os_delete(asset_path.raw());
+    os_delete(tmp_proj_path);
+    genesis_context_destroy(context);
+}
+
+static void test_project_format_position(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+    static const char *tmp_proj_path = "/tmp/test_genesis_format_position.gdaw";
+    os_delete(tmp_proj_path);
+
+    User *user = user_create(uint256::random(), os_get_user_name());
+    Project *project;
+    ok_or_panic(project_create(context, tmp_proj_path, uint256::random(), user, &project));
+
+    // 140 whole notes is one minute
+    ByteBuffer buf;
+    project_format_position(project, 140.0, TimeFormatSeconds, buf);
+    assert(ByteBuffer::compare(buf, "60.000") == 0);
+    project_format_position(project, 140.0, TimeFormatMinSec, buf);
+    assert(ByteBuffer::compare(buf, "1:00") == 0);
+    project_format_position(project, 140.0, TimeFormatMinSecMs, buf);
+    assert(ByteBuffer::compare(buf, "1:00.000") == 0);
+    project_format_position(project, 140.0, TimeFormatBarsBeats, buf);
+    assert(ByteBuffer::compare(buf, "141.1.000") == 0);
+    project_format_position(project, 2.5, TimeFormatBarsBeats, buf);
+    assert(ByteBuffer::compare(buf, "3.3.000") == 0);
+
+    ByteBuffer expected_frames;
+    expected_frames.format("%d", project->sample_rate * 60);
+    project_format_position(project, 140.0, TimeFormatFrames, buf);
+    assert(ByteBuffer::compare(buf, expected_frames) == 0);
+
+    project_close(project);
+    user_destroy(user);
+    os_delete(tmp_proj_path);
+    genesis_context_destroy(context);
+}
+
 static void test_string_compare(void) {
     String a("67 fps");
     String b("69 fps");
@@ -399,6 +565,58 @@ static void test_string_compare(void) {
     assert(String::compare_insensitive(b, a) == 1);
 }
 
+static void test_string_is_combining_mark(void) {
+    assert(String::is_combining_mark(0x301)); // combining acute accent
+    assert(String::is_combining_mark(0x20dd)); // combining enclosing circle
+    assert(!String::is_combining_mark('e'));
+    assert(!String::is_combining_mark(0xe9)); // precomposed e with acute
+    assert(!String::is_combining_mark(' '));
+}
+
+static void test_string_normalize_nfc(void) {
+    String precomposed;
+    precomposed.append(0xe9);
+    String decomposed;
+    decomposed.append('e');
+    decomposed.append(0x301);
+    decomposed.normalize_nfc();
+    assert(String::equal(decomposed, precomposed));
+    precomposed.normalize_nfc();
+    assert(precomposed.length() == 1);
+    assert(precomposed.at(0) == 0xe9);
+
+    // marks are reordered by combining class before composing, so both
+    // orders of dot below and circumflex give U+1EAD
+    String a1;
+    a1.append('a');
+    a1.append(0x302);
+    a1.append(0x323);
+    a1.normalize_nfc();
+    String a2;
+    a2.append('a');
+    a2.append(0x323);
+    a2.append(0x302);
+    a2.normalize_nfc();
+    assert(a1.length() == 1);
+    assert(a1.at(0) == 0x1ead);
+    assert(String::equal(a1, a2));
+
+    // hangul jamo compose to a syllable
+    String hangul;
+    hangul.append(0x1100);
+    hangul.append(0x1161);
+    hangul.append(0x11a8);
+    hangul.normalize_nfc();
+    assert(hangul.length() == 1);
+    assert(hangul.at(0) == 0xac01);
+
+    // excluded compositions stay decomposed
+    String devanagari;
+    devanagari.append(0x958);
+    devanagari.normalize_nfc();
+    assert(devanagari.length() == 2);
+}
+
 static void test_audio_file(void) {
     static const char *tmp_file_path = "/tmp/test_genesis_out.flac";
 
@@ -425,12 +643,179 @@ static void test_audio_file(void) {
     os_delete(tmp_file_path);
 }
 
+static float frame_sum(GenesisAudioFile *audio_file, long frame) {
+    float sum = 0.0f;
+    int channel_count = genesis_audio_file_channel_layout(audio_file)->channel_count;
+    for (int ch = 0; ch < channel_count; ch += 1) {
+        GenesisAudioFileIterator it = genesis_audio_file_iterator(audio_file, ch, frame);
+        sum += it.ptr[frame - it.start];
+    }
+    return sum;
+}
+
+static void test_audio_file_stereo_correlation(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+
+    GenesisAudioFile *audio_file = ok_mem(genesis_audio_file_create(context, 48000));
+    double correlation;
+    assert(genesis_audio_file_stereo_correlation(audio_file, 0, 0, &correlation) ==
+            GenesisErrorIncompatibleChannelLayouts);
+
+    ok_or_panic(genesis_audio_file_set_channel_layout(audio_file,
+                soundio_channel_layout_get_builtin(SoundIoChannelLayoutIdStereo)));
+    List<float> &left = audio_file->channels.at(0).samples;
+    List<float> &right = audio_file->channels.at(1).samples;
+    static const int frame_count = 480;
+    for (int frame = 0; frame < frame_count; frame += 1) {
+        float sample = sinf(frame * 2.0f * (float)M_PI / 48.0f);
+        ok_or_panic(left.append(sample));
+        ok_or_panic(right.append(sample));
+    }
+    ok_or_panic(genesis_audio_file_stereo_correlation(audio_file, 0, frame_count, &correlation));
+    assert(fabs(correlation - 1.0) < 0.0001);
+
+    for (int frame = 0; frame < frame_count; frame += 1)
+        right.at(frame) = -left.at(frame);
+    ok_or_panic(genesis_audio_file_stereo_correlation(audio_file, 0, frame_count, &correlation));
+    assert(fabs(correlation + 1.0) < 0.0001);
+
+    // a quarter cycle apart over whole cycles
+    for (int frame = 0; frame < frame_count; frame += 1)
+        right.at(frame) = left.at((frame + 12) % frame_count);
+    ok_or_panic(genesis_audio_file_stereo_correlation(audio_file, 0, frame_count, &correlation));
+    assert(fabs(correlation) < 0.0001);
+
+    for (int frame = 0; frame < frame_count; frame += 1)
+        right.at(frame) = 0.0f;
+    ok_or_panic(genesis_audio_file_stereo_correlation(audio_file, 0, frame_count, &correlation));
+    assert(correlation == 0.0);
+
+    genesis_audio_file_destroy(audio_file);
+    genesis_context_destroy(context);
+}
+
+static void test_audio_file_test_decoder(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+
+    TestDecoder test_decoder;
+    test_decoder_init(&test_decoder, TestWaveformSine, 1000.0, 48000, 0.1);
+    genesis_set_audio_file_decoder(context, &test_decoder.decoder);
+
+    GenesisAudioFile *audio_file;
+    ok_or_panic(genesis_audio_file_load(context, "sine", &audio_file));
+    assert(genesis_audio_file_sample_rate(audio_file) == 48000);
+    assert(genesis_audio_file_channel_layout(audio_file)->channel_count == 1);
+    long frame_count = genesis_audio_file_frame_count(audio_file);
+    assert(frame_count == 4800);
+    assert(genesis_audio_file_container_duration(audio_file) == 0.1);
+
+    // 48 frames per cycle, so every column of whole cycles peaks at exactly
+    // a quarter and three quarters of the way through one
+    static const int column_frame_count = 480;
+    GenesisAudioFileIterator it = genesis_audio_file_iterator(audio_file, 0, 0);
+    for (long start = 0; start < frame_count; start += column_frame_count) {
+        float col_min = 0.0f;
+        float col_max = 0.0f;
+        for (long frame = start; frame < start + column_frame_count; frame += 1) {
+            col_min = min(col_min, it.ptr[frame - it.start]);
+            col_max = max(col_max, it.ptr[frame - it.start]);
+        }
+        assert(fabsf(col_max - 1.0f) < 0.0001f);
+        assert(fabsf(col_min + 1.0f) < 0.0001f);
+    }
+    // the first half cycle stays above zero
+    for (long frame = 0; frame < 24; frame += 1)
+        assert(it.ptr[frame] >= 0.0f);
+    genesis_audio_file_destroy(audio_file);
+
+    genesis_set_audio_file_decoder(context, nullptr);
+    genesis_context_destroy(context);
+}
+
+static void test_audio_file_zero_crossing(void) {
+    GenesisContext *context;
+    ok_or_panic(genesis_context_create(&context));
+
+    GenesisAudioFile *audio_file;
+    ok_or_panic(genesis_audio_file_load(context, "../test/tiny-sine.ogg", &audio_file));
+
+    // a sine wave crosses zero at least once per cycle
+    long max_distance = genesis_audio_file_sample_rate(audio_file) / 100;
+    long target = genesis_audio_file_frame_count(audio_file) / 2;
+    long frame = genesis_audio_file_find_zero_crossing(audio_file, target, max_distance);
+    assert(labs(frame - target) <= max_distance);
+    float prev = frame_sum(audio_file, frame - 1);
+    float cur = frame_sum(audio_file, frame);
+    assert(cur == 0.0f || (prev < 0.0f) != (cur < 0.0f));
+
+    // frame 0 has no previous frame to cross from
+    long first = genesis_audio_file_find_zero_crossing(audio_file, 0, 0);
+    assert(first == 0);
+
+    genesis_audio_file_destroy(audio_file);
+    genesis_context_destroy(context);
+}
+
+static bool parses_to(const char *str, int modifiers, VirtKey key) {
+    KeySequence seq;
+    if (!key_sequence_from_string(str, &seq))
+        return false;
+    return seq.modifiers == modifiers && seq.key == key;
+}
+
+static void test_key_sequence_from_string(void) {
+    assert(parses_to("Z", 0, VirtKeyZ));
+    assert(parses_to("Space", 0, VirtKeySpace));
+    assert(parses_to("Ctrl+Z", KeyModControl, VirtKeyZ));
+    assert(parses_to("Ctrl+Shift+Z", KeyModControl|KeyModShift, VirtKeyZ));
+    assert(parses_to("Shift+Ctrl+Z", KeyModControl|KeyModShift, VirtKeyZ));
+    assert(parses_to("Alt+F4", KeyModAlt, VirtKeyF4));
+    assert(parses_to("Ctrl+Shift+Alt+Super+Left", KeyModControl|KeyModShift|KeyModAlt|KeyModSuper,
+                VirtKeyLeft));
+    assert(parses_to("Ctrl+-", KeyModControl, VirtKeyMinus));
+
+    KeySequence seq;
+    assert(key_sequence_from_string("", &seq));
+    assert(null_key_sequence(seq));
+
+    assert(!key_sequence_from_string("Banana", &seq));
+    assert(!key_sequence_from_string("Ctrl+Banana", &seq));
+    assert(!key_sequence_from_string("Unknown", &seq));
+    assert(!key_sequence_from_string("Ctrl+", &seq));
+    assert(!key_sequence_from_string("ctrl+z", &seq));
+    assert(!key_sequence_from_string("Ctrl+Z ", &seq));
+
+    for (int i = 0; i < KeyBindingActionCount; i += 1) {
+        KeySequence default_seq = key_binding_default((KeyBindingAction)i);
+        if (null_key_sequence(default_seq))
+            continue;
+        ByteBuffer str = key_sequence_to_string(default_seq).encode();
+        assert(key_sequence_from_string(str, &seq));
+        assert(seq.modifiers == default_seq.modifiers);
+        assert(seq.key == default_seq.key);
+    }
+}
+
 static void test_path_extension(void) {
     assert(ByteBuffer::compare(os_path_extension("foo"), "") == 0);
     assert(ByteBuffer::compare(os_path_extension("foo.ogg"), ".ogg") == 0);
     assert(ByteBuffer::compare(os_path_extension("foo.ogg.flac"), ".flac") == 0);
 }
 
+static void test_url_extension(void) {
+    assert(ByteBuffer::compare(os_url_extension("https://example.com/a/b.ogg"), ".ogg") == 0);
+    assert(ByteBuffer::compare(os_url_extension("http://example.com/b.flac?x=1.mp3#y.wav"), ".flac") == 0);
+    assert(ByteBuffer::compare(os_url_extension("http://example.com/dir.d/b"), "") == 0);
+    assert(ByteBuffer::compare(os_url_extension("http://example.com/b."), "") == 0);
+    assert(ByteBuffer::compare(os_url_extension("http://example.com"), "") == 0);
+    assert(ByteBuffer::compare(os_url_extension("http://example.com?q=a.ogg"), "") == 0);
+    // no scheme
+    assert(ByteBuffer::compare(os_url_extension("example.com/b.ogg"), ".ogg") == 0);
+    assert(ByteBuffer::compare(os_url_extension(""), "") == 0);
+}
+
 static void test_atomic_value(void) {
     AtomicValue<int> av;
 
@@ -459,6 +844,362 @@ static void test_atomic_double(void) {
     assert(x.load() == 13.0);
 }
 
+static void test_notdef_box(void) {
+    NotdefBox box = notdef_box(NotdefStyleHidden, 12);
+    assert(box.advance == 0);
+    assert(box.left == 0);
+    assert(box.top == 0);
+    assert(box.width == 0);
+    assert(box.height == 0);
+
+    // on the baseline, with a pixel of room either side
+    box = notdef_box(NotdefStyleHollowBox, 12);
+    assert(box.advance == 7);
+    assert(box.left == 1);
+    assert(box.width == box.advance - 2);
+    assert(box.height == 8);
+    assert(box.top == box.height);
+    assert(box.stroke == 1);
+
+    box = notdef_box(NotdefStyleHollowBox, 24);
+    assert(box.advance == 14);
+    assert(box.width == 12);
+    assert(box.height == 17);
+    assert(box.stroke == 2);
+
+    ByteBuffer pixels;
+    notdef_box_pixels(box, pixels);
+    assert(pixels.length() == box.width * box.height);
+    for (int y = 0; y < box.height; y += 1) {
+        for (int x = 0; x < box.width; x += 1) {
+            bool edge = x < 2 || x >= box.width - 2 || y < 2 || y >= box.height - 2;
+            assert((uint8_t)pixels.at(y * box.width + x) == (edge ? 255 : 0));
+        }
+    }
+
+    // too small to be hollow
+    box = notdef_box(NotdefStyleHollowBox, 1);
+    assert(box.advance == 3);
+    assert(box.width == 1);
+    assert(box.height == 3);
+    notdef_box_pixels(box, pixels);
+    for (int i = 0; i < pixels.length(); i += 1)
+        assert((uint8_t)pixels.at(i) == 255);
+}
+
+static void test_redraw_state(void) {
+    RedrawState redraw_state;
+    assert(!redraw_state.needs_continuous_redraw());
+
+    // an animation calls keep_animating() every iteration until it is done
+    for (int i = 0; i < 3; i += 1) {
+        redraw_state.keep_animating();
+        assert(redraw_state.end_iteration());
+        assert(redraw_state.needs_continuous_redraw());
+    }
+
+    // the first iteration without a call ends the continuous redraw
+    assert(!redraw_state.end_iteration());
+    assert(!redraw_state.needs_continuous_redraw());
+    assert(!redraw_state.end_iteration());
+}
+
+static void test_fixed_timestep(void) {
+    // a second at 16, 128 and an uneven mix of frame rates. the times are
+    // powers of two so that the sums are exact
+    static const double timestep = 1.0 / 64.0;
+    FixedTimestep slow;
+    int slow_steps = 0;
+    for (int i = 0; i < 16; i += 1)
+        slow_steps += slow.advance(timestep, 1.0 / 16.0);
+    assert(slow_steps == 64);
+
+    FixedTimestep fast;
+    int fast_steps = 0;
+    for (int i = 0; i < 128; i += 1) {
+        int step_count = fast.advance(timestep, 1.0 / 128.0);
+        // every other frame is too short for a step of its own
+        assert(step_count == i % 2);
+        fast_steps += step_count;
+    }
+    assert(fast_steps == 64);
+
+    FixedTimestep uneven;
+    int uneven_steps = 0;
+    for (int i = 0; i < 8; i += 1) {
+        uneven_steps += uneven.advance(timestep, 1.0 / 32.0);
+        uneven_steps += uneven.advance(timestep, 3.0 / 256.0);
+        uneven_steps += uneven.advance(timestep, 21.0 / 256.0);
+    }
+    assert(uneven_steps == 64);
+}
+
+static void test_modal_mouse_target(void) {
+    MouseEvent event = {};
+    event.x = 150;
+    event.y = 150;
+    event.action = MouseActionDown;
+    event.button = MouseButtonLeft;
+    assert(modal_mouse_target(&event, 100, 100, 200, 100) == ModalMouseTargetModal);
+
+    // a click outside dismisses the modal and is not passed on
+    event.x = 50;
+    assert(modal_mouse_target(&event, 100, 100, 200, 100) == ModalMouseTargetDismiss);
+    event.x = 300;
+    assert(modal_mouse_target(&event, 100, 100, 200, 100) == ModalMouseTargetDismiss);
+
+    // neither is anything else outside
+    event.action = MouseActionUp;
+    assert(modal_mouse_target(&event, 100, 100, 200, 100) == ModalMouseTargetNone);
+    event.action = MouseActionMove;
+    event.button = MouseButtonNone;
+    assert(modal_mouse_target(&event, 100, 100, 200, 100) == ModalMouseTargetNone);
+}
+
+static void test_rich_text_layout(void) {
+    // advance, width, above, below, line break before
+    List<RichTextSpanMetrics> spans;
+    ok_or_panic(spans.append(RichTextSpanMetrics {40, 38, 10, 3, false}));
+    ok_or_panic(spans.append(RichTextSpanMetrics {30, 30, 16, 5, false}));
+    ok_or_panic(spans.append(RichTextSpanMetrics {20, 18, 8, 2, true}));
+    ok_or_panic(spans.append(RichTextSpanMetrics {25, 22, 9, 4, false}));
+
+    List<RichTextSpanPos> positions;
+    List<RichTextLine> lines;
+    int width;
+    int height;
+    rich_text_layout(spans, positions, lines, &width, &height);
+
+    // each line takes the largest ascent and the largest descent of its spans
+    assert(lines.length() == 2);
+    assert(lines.at(0).top == 0);
+    assert(lines.at(0).above_size == 16);
+    assert(lines.at(0).below_size == 5);
+    assert(lines.at(1).top == 21);
+    assert(lines.at(1).above_size == 9);
+    assert(lines.at(1).below_size == 4);
+    assert(height == 34);
+
+    // spans share their line's baseline
+    assert(positions.length() == 4);
+    assert(positions.at(0).left == 0);
+    assert(positions.at(0).top == 6);
+    assert(positions.at(1).left == 40);
+    assert(positions.at(1).top == 0);
+    assert(positions.at(2).left == 0);
+    assert(positions.at(2).top == 22);
+    assert(positions.at(3).left == 20);
+    assert(positions.at(3).top == 21);
+
+    // the first line is widest, to the last inked pixel of its second span
+    assert(width == 70);
+
+    spans.clear();
+    rich_text_layout(spans, positions, lines, &width, &height);
+    assert(lines.length() == 0);
+    assert(width == 0);
+    assert(height == 0);
+}
+
+static void test_label_sampling(void) {
+    LabelSampling sampling = label_sampling(LabelFilterLinear, 1);
+    assert(!sampling.nearest_min);
+    assert(!sampling.nearest_mag);
+
+    sampling = label_sampling(LabelFilterNearest, 1);
+    assert(sampling.nearest_min);
+    assert(sampling.nearest_mag);
+
+    // integer scaling keeps magnified texels square but not minified ones
+    sampling = label_sampling(LabelFilterLinear, 2);
+    assert(!sampling.nearest_min);
+    assert(sampling.nearest_mag);
+}
+
+static const char *bundled_font_path = "../assets/font/OpenSans-Regular.ttf";
+
+static FontLibrary *create_bundled_font_library(void) {
+    ByteBuffer font_buffer;
+    ok_or_panic(os_read_file(bundled_font_path, font_buffer));
+    return create<FontLibrary>(font_buffer);
+}
+
+static void test_font_library_reload(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    FontSize *font_size = font_library->get_font_size(12);
+    int prefilled_count = font_library->glyph_cache_count();
+    assert(prefilled_count > 0);
+
+    ByteBuffer text("abc");
+    List<TextLayoutLetter> letters;
+    font_size->font_cache_entry(0xe9);
+    font_size->put_layout(text, letters, 10);
+    assert(font_library->glyph_cache_count() == prefilled_count + 1);
+    assert(font_size->get_layout(text));
+
+    // the face at every size is replaced and nothing laid out with the old
+    // one is left behind
+    int generation = font_library->_font_generation;
+    ok_or_panic(font_library->reload_font(bundled_font_path));
+    assert(font_library->_font_generation != generation);
+    assert(font_library->glyph_cache_count() == prefilled_count);
+    assert(!font_size->get_layout(text));
+    assert(font_size->font_face() == font_library->_default_font_face);
+
+    font_size->font_cache_entry(0xe9);
+    font_size->put_layout(text, letters, 10);
+    generation = font_library->_font_generation;
+    font_library->clear_cache();
+    assert(font_library->_font_generation != generation);
+    assert(font_library->glyph_cache_count() == prefilled_count);
+    assert(!font_size->get_layout(text));
+
+    assert(font_library->reload_font("../test/no-such-font.ttf") == GenesisErrorFileAccess);
+
+    destroy(font_library, 1);
+}
+
+static void test_font_library_charset(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    assert(font_library->has_glyph('A'));
+    assert(font_library->has_glyph(0xe9));
+    // Open Sans has no CJK
+    assert(!font_library->has_glyph(0x4e2d));
+
+    List<uint32_t> charset;
+    font_library->get_charset(charset);
+    assert(charset.length() > 0);
+    bool found_a = false;
+    for (int i = 0; i < charset.length(); i += 1) {
+        uint32_t codepoint = charset.at(i);
+        assert(i == 0 || charset.at(i - 1) < codepoint);
+        assert(font_library->has_glyph(codepoint));
+        assert(codepoint != 0x4e2d);
+        if (codepoint == 'A')
+            found_a = true;
+    }
+    assert(found_a);
+
+    destroy(font_library, 1);
+}
+
+static void test_label_layout_vertical(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    FontSize *font_size = font_library->get_font_size(12);
+    FontCacheValue a = font_size->font_cache_entry('a');
+    FontCacheValue b = font_size->font_cache_entry('b');
+
+    List<TextLayoutLetter> letters;
+    int width;
+    int height;
+    label_layout_letters_vertical(font_size, "ab", letters, &width, &height);
+
+    // stacked in one column, each one vertical advance below the last
+    assert(letters.length() == 2);
+    assert(letters.at(0).top == 0);
+    assert(letters.at(1).top == a.vert_advance);
+    assert(height == a.vert_advance + b.vert_advance);
+
+    // centered on the column, which is wide enough for both glyphs
+    for (int i = 0; i < letters.length(); i += 1) {
+        TextLayoutLetter *letter = &letters.at(i);
+        assert(letter->left == 0);
+        assert(-letter->bitmap_left <= width / 2);
+        assert(letter->bitmap_left + letter->bitmap_width <= width / 2);
+    }
+    assert(width > 0);
+
+    destroy(font_library, 1);
+}
+
+static void test_label_layout_oversample(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    FontSize *font_size = font_library->get_font_size(24);
+    int line_height = font_size->_max_above_size + font_size->_max_below_size;
+
+    List<TextLayoutLetter> letters;
+    label_layout_letters(font_size, "ab\ncd", true, letters);
+    List<LabelLine> raster_lines;
+    assert(label_layout_lines(font_size, 1.0f, letters, raster_lines) == line_height * 2);
+
+    // oversampled twice, the lines are measured in pixels half the size of
+    // the raster ones, while the returned height stays in raster pixels
+    List<LabelLine> lines;
+    assert(label_layout_lines(font_size, 2.0f, letters, lines) == line_height * 2);
+    assert(lines.length() == 2);
+    for (int i = 0; i < lines.length(); i += 1) {
+        LabelLine *line = &lines.at(i);
+        LabelLine *raster_line = &raster_lines.at(i);
+        assert(line->start == raster_line->start);
+        assert(line->end == raster_line->end);
+        assert(line->top == raster_line->top / 2);
+        assert(line->height == (raster_line->height + 1) / 2);
+        assert(line->width == (raster_line->width + 1) / 2);
+        assert(line->baseline == raster_line->baseline / 2);
+    }
+    assert(lines.at(0).width > 0);
+
+    destroy(font_library, 1);
+}
+
+static void test_label_layout_kerning(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    FontSize *font_size = font_library->get_font_size(48);
+
+    // the kern table of the bundled font pulls an A under a quotation mark
+    List<TextLayoutLetter> kerned;
+    float kerned_width = label_layout_letters(font_size, "\"A", true, kerned);
+    List<TextLayoutLetter> unkerned;
+    float unkerned_width = label_layout_letters(font_size, "\"A", false, unkerned);
+    assert(kerned.length() == 2);
+    assert(unkerned.length() == 2);
+    TextLayoutLetter *kerned_a = &kerned.at(1);
+    TextLayoutLetter *unkerned_a = &unkerned.at(1);
+    assert(kerned_a->left + kerned_a->bitmap_left < unkerned_a->left + unkerned_a->bitmap_left);
+    assert(kerned_width < unkerned_width);
+
+    destroy(font_library, 1);
+}
+
+static void test_label_layout_lines(void) {
+    FontLibrary *font_library = create_bundled_font_library();
+    FontSize *font_size = font_library->get_font_size(12);
+    int above_size = font_size->_max_above_size;
+    int line_height = above_size + font_size->_max_below_size;
+
+    List<TextLayoutLetter> letters;
+    float width = label_layout_letters(font_size, "ab\nwide line\n", true, letters);
+    List<LabelLine> lines;
+    assert(label_layout_lines(font_size, 1.0f, letters, lines) == line_height * 3);
+
+    // each break ends its line and is the letter at the line's end
+    assert(lines.length() == 3);
+    assert(lines.at(0).start == 0);
+    assert(lines.at(0).end == 2);
+    assert(letters.at(2).codepoint == '\n');
+    assert(lines.at(1).start == 3);
+    assert(lines.at(1).end == 12);
+    assert(lines.at(2).start == 13);
+    assert(lines.at(2).end == 13);
+
+    for (int i = 0; i < lines.length(); i += 1) {
+        LabelLine *line = &lines.at(i);
+        assert(line->left == 0);
+        assert(line->top == i * line_height);
+        assert(line->height == line_height);
+        assert(line->baseline == line->top + above_size);
+    }
+
+    // the second line is the widest, and the one after the trailing break
+    // is empty
+    assert(lines.at(0).width > 0);
+    assert(lines.at(1).width > lines.at(0).width);
+    assert(lines.at(1).width == (int)width);
+    assert(lines.at(2).width == 0);
+
+    destroy(font_library, 1);
+}
+
 static void test_mirrored_memory(void) {
     struct OsMirroredMemory mem;
 
@@ -488,10 +1229,12 @@ static struct Test tests[] = {
     {"List::remove_range", test_list_remove_range},
     {"List::insert_space", test_list_insert_space},
     {"parse_color", test_parse_color},
+    {"color_contrast_ratio", test_color_contrast_ratio},
     {"RingBuffer", test_ring_buffer},
     {"euclidean_mod", test_euclidean_mod},
     {"ThreadSafeQueue", test_thread_safe_queue},
     {"greatest_common_denominator", test_gcd},
+    {"next_power_of_two", test_next_power_of_two},
     {"sort keys basic", test_sort_keys_basic},
     {"sort keys count", test_sort_keys_count},
     {"LockedQueue", test_locked_queue},
@@ -503,11 +1246,33 @@ static struct Test tests[] = {
     {"ByteBuffer::to_string", test_byte_buffer_to_string},
     {"List::sort", test_list_sort},
     {"basic project editing", test_basic_project_editing},
+    {"project duration uses clip sample rate", test_project_duration_sample_rate},
+    {"project_snap_to_zero_crossing", test_project_snap_to_zero_crossing},
+    {"project_format_position", test_project_format_position},
     {"String::compare", test_string_compare},
+    {"String::is_combining_mark", test_string_is_combining_mark},
+    {"String::normalize_nfc", test_string_normalize_nfc},
     {"basic audio file loading and saving", test_audio_file},
+    {"genesis_audio_file_find_zero_crossing", test_audio_file_zero_crossing},
+    {"genesis_audio_file_stereo_correlation", test_audio_file_stereo_correlation},
+    {"loading from a synthetic decoder", test_audio_file_test_decoder},
+    {"key_sequence_from_string", test_key_sequence_from_string},
     {"os_path_extension", test_path_extension},
+    {"os_url_extension", test_url_extension},
     {"AtomicValue", test_atomic_value},
     {"AtomicDouble", test_atomic_double},
+    {"notdef_box", test_notdef_box},
+    {"RedrawState", test_redraw_state},
+    {"FixedTimestep", test_fixed_timestep},
+    {"modal_mouse_target", test_modal_mouse_target},
+    {"rich_text_layout", test_rich_text_layout},
+    {"label_sampling", test_label_sampling},
+    {"FontLibrary::reload_font", test_font_library_reload},
+    {"FontLibrary::get_charset", test_font_library_charset},
+    {"label_layout_letters_vertical", test_label_layout_vertical},
+    {"label_layout_lines oversample", test_label_layout_oversample},
+    {"label_layout_letters kerning", test_label_layout_kerning},
+    {"label_layout_lines", test_label_layout_lines},
     {NULL, NULL},
 };
 